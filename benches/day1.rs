@@ -0,0 +1,101 @@
+use std::hint::black_box;
+
+use aoc2023::{
+    day1_part1, day1_part2, read_input, Day1Decoder, Day1NoDigitsPolicy, DAY1_DIGITS,
+    DAY1_ENGLISH_WORDS,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+// The original part 1, which finds byte offsets and then walks the chars to them
+fn day1_part1_find_rfind(inp: &str) -> u32 {
+    inp.lines()
+        .map(|line: &str| {
+            let i = line.find(|c: char| c.is_numeric()).unwrap();
+            let j = line.rfind(|c: char| c.is_numeric()).unwrap();
+            let ii = line.chars().nth(i).unwrap().to_digit(10).unwrap();
+            let jj = line.chars().nth(j).unwrap().to_digit(10).unwrap();
+            ii * 10 + jj
+        })
+        .sum()
+}
+
+// The original part 2, which runs find and rfind once per token
+fn day1_part2_find_rfind(inp: &str) -> u32 {
+    let digits_map = DAY1_DIGITS
+        .into_iter()
+        .chain(DAY1_ENGLISH_WORDS)
+        .collect::<Vec<_>>();
+    inp.lines()
+        .map(|line: &str| {
+            let left_num = digits_map
+                .iter()
+                .filter_map(|(s, n)| line.find(s).map(|i| (i, n)))
+                .min_by_key(|&(i, _)| i)
+                .unwrap()
+                .1;
+            let right_num = digits_map
+                .iter()
+                .filter_map(|(s, n)| line.rfind(s).map(|i| (i, n)))
+                .max_by_key(|&(i, _)| i)
+                .unwrap()
+                .1;
+            left_num * 10 + right_num
+        })
+        .sum()
+}
+
+/// Roughly `size` bytes of calibration lines, each with at least one digit, built with
+/// a fixed-seed LCG so every run benchmarks the same input.
+fn generate_input(size: usize) -> String {
+    let alphabet = b"abcdefghijklmnopqrstuvwxyz";
+    let mut state: u64 = 0x2023_0001;
+    let mut next = |n: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) as usize) % n
+    };
+    let mut inp = String::with_capacity(size + 64);
+    while inp.len() < size {
+        let line_len = 8 + next(40);
+        let digit_pos = next(line_len);
+        for i in 0..line_len {
+            match next(10) {
+                _ if i == digit_pos => inp.push(char::from(b'0' + next(10) as u8)),
+                0 => inp.push_str(DAY1_ENGLISH_WORDS[next(9)].0),
+                1 => inp.push(char::from(b'0' + next(10) as u8)),
+                _ => inp.push(char::from(alphabet[next(alphabet.len())])),
+            }
+        }
+        inp.push('\n');
+    }
+    inp
+}
+
+fn bench_day1(c: &mut Criterion) {
+    let real_inp = read_input("inputs/default/day1.txt").unwrap();
+    let generated_inp = generate_input(4 << 20);
+    let decoder = Day1Decoder::english();
+    for (name, inp) in [("day1.txt", &real_inp), ("generated_4mb", &generated_inp)] {
+        let mut group = c.benchmark_group(format!("day1/{}", name));
+        group.throughput(Throughput::Bytes(inp.len() as u64));
+        if inp.len() > 1 << 20 {
+            group.sample_size(10);
+        }
+        group.bench_function("part1/find_rfind", |b| {
+            b.iter(|| day1_part1_find_rfind(black_box(inp)))
+        });
+        group.bench_function("part1/byte_scan", |b| b.iter(|| day1_part1(black_box(inp))));
+        group.bench_function("part2/find_rfind", |b| {
+            b.iter(|| day1_part2_find_rfind(black_box(inp)))
+        });
+        group.bench_function("part2/aho_corasick", |b| {
+            b.iter(|| decoder.decode(black_box(inp), Day1NoDigitsPolicy::Error))
+        });
+        group.bench_function("part2/byte_scan", |b| b.iter(|| day1_part2(black_box(inp))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_day1);
+criterion_main!(benches);
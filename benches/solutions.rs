@@ -0,0 +1,232 @@
+//! Times every registered parser and solver on its real input and on a larger generated
+//! input, parsing separately from solving, and appends the results to
+//! `benches/history.jsonl` (one JSON object per measurement, tagged with the commit it was
+//! taken at). Each measurement is compared with the latest one from a different commit so
+//! regressions stand out. The history is tracked by git so that it survives `cargo clean`
+//! and can be shared; set `AOC_BENCH_HISTORY` to keep it somewhere else.
+//!
+//! `cargo bench --bench solutions -- [FILTER]... [--skip FILTER]...`
+//!
+//! Filters are matched as substrings of `dayN/<stage>/<input>`, where the stage is `parse`,
+//! `part1` or `part2`, e.g. `day5/part1` or `--skip day5/part2`.
+
+use std::{
+    env, fs,
+    fs::OpenOptions,
+    hint::black_box,
+    io::Write,
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use aoc2023::{
+    cancel::CancellationToken,
+    generators,
+    inputs::{InputStore, DEFAULT_PROFILE},
+    observer::Silent,
+    SOLUTIONS,
+};
+use serde_json::{json, Value};
+
+const DEFAULT_HISTORY_PATH: &str = "benches/history.jsonl";
+const MAX_SAMPLES: usize = 10;
+const TIME_BUDGET: Duration = Duration::from_secs(2);
+/// Slowdowns above this fraction are flagged.
+const REGRESSION_THRESHOLD: f64 = 0.1;
+
+/// Size passed to `generators::generate` for the larger input of each day.
+const GENERATED_SIZES: [(u8, usize); 9] = [
+    (1, 10_000),
+    (2, 1_000),
+    (3, 400),
+    (4, 2_000),
+    (5, 20),
+    (6, 5),
+    (7, 10_000),
+    (8, 1_000),
+    (9, 2_000),
+];
+
+/// The real input plus a larger generated one, if there are any. Days that are still
+/// being written have neither.
+fn inputs_for(day: u8) -> Vec<(String, String)> {
+    let store = InputStore::new("inputs", DEFAULT_PROFILE);
+    if !store.has(day) {
+        return Vec::new();
+    }
+    let mut inputs = vec![(format!("day{}.txt", day), store.get(day).unwrap())];
+    if let Some(&(_, size)) = GENERATED_SIZES.iter().find(|&&(d, _)| d == day) {
+        inputs.push((
+            format!("generated_{}", size),
+            generators::generate(day, 0, size).unwrap(),
+        ));
+    }
+    inputs
+}
+
+struct Measurement {
+    samples: Vec<Duration>,
+}
+
+impl Measurement {
+    fn take(mut f: impl FnMut()) -> Self {
+        let mut samples = Vec::new();
+        let start = Instant::now();
+        while samples.len() < MAX_SAMPLES && (samples.is_empty() || start.elapsed() < TIME_BUDGET) {
+            let sample_start = Instant::now();
+            f();
+            samples.push(sample_start.elapsed());
+        }
+        samples.sort();
+        Measurement { samples }
+    }
+
+    fn median_ns(&self) -> u128 {
+        self.samples[self.samples.len() / 2].as_nanos()
+    }
+
+    fn min_ns(&self) -> u128 {
+        self.samples[0].as_nanos()
+    }
+
+    fn mean_ns(&self) -> u128 {
+        self.samples.iter().sum::<Duration>().as_nanos() / self.samples.len() as u128
+    }
+}
+
+fn history_path() -> String {
+    env::var("AOC_BENCH_HISTORY").unwrap_or(DEFAULT_HISTORY_PATH.to_owned())
+}
+
+/// The current commit, marked dirty if anything but the history has changed since.
+fn git_commit(history_path: &str) -> String {
+    let run = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+    match (
+        run(&["rev-parse", "--short", "HEAD"]),
+        run(&[
+            "status",
+            "--porcelain",
+            "--",
+            ".",
+            &format!(":!{}", history_path),
+        ]),
+    ) {
+        (Some(commit), Some(status)) if !status.is_empty() => format!("{}-dirty", commit),
+        (Some(commit), _) => commit,
+        (None, _) => "unknown".to_owned(),
+    }
+}
+
+fn load_history(history_path: &str) -> Vec<Value> {
+    fs::read_to_string(history_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn main() {
+    let mut filters = Vec::new();
+    let mut skips = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--skip" => skips.extend(args.next()),
+            // Passed by `cargo bench`
+            "--bench" => {}
+            other => filters.push(other.to_owned()),
+        }
+    }
+
+    let history_path = history_path();
+    let commit = git_commit(&history_path);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let history = load_history(&history_path);
+    let mut history_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .unwrap_or_else(|e| panic!("{}: {}", history_path, e));
+
+    let cancel = CancellationToken::new();
+    for solution in SOLUTIONS.iter() {
+        for (input_name, inp) in inputs_for(solution.day) {
+            // A new day's placeholder parser rejects even a real input until it's written
+            let parsed = match (solution.parse)(&inp) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("day{}/{}: skipped: {}", solution.day, input_name, e);
+                    continue;
+                }
+            };
+            for part in [None, Some(1), Some(2)] {
+                let stage = match part {
+                    Some(part) => format!("part{}", part),
+                    None => "parse".to_owned(),
+                };
+                let name = format!("day{}/{}/{}", solution.day, stage, input_name);
+                if (!filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())))
+                    || skips.iter().any(|s| name.contains(s.as_str()))
+                {
+                    continue;
+                }
+                let measurement = match part {
+                    Some(part) => {
+                        let solve = solution.parts[part - 1];
+                        Measurement::take(|| {
+                            black_box(solve(black_box(&*parsed), &Silent, &cancel)).unwrap();
+                        })
+                    }
+                    None => Measurement::take(|| {
+                        black_box((solution.parse)(black_box(&inp))).unwrap();
+                    }),
+                };
+                let previous = history.iter().rev().find(|record| {
+                    record["name"] == name.as_str() && record["commit"] != commit.as_str()
+                });
+                let change = previous
+                    .and_then(|record| record["median_ns"].as_f64())
+                    .map(|previous_ns| {
+                        (measurement.median_ns() as f64 - previous_ns) / previous_ns
+                    });
+                println!(
+                    "{:<32} median {:>12.3?}  min {:>12.3?}  ({} samples){}",
+                    name,
+                    Duration::from_nanos(measurement.median_ns() as u64),
+                    Duration::from_nanos(measurement.min_ns() as u64),
+                    measurement.samples.len(),
+                    match change {
+                        Some(c) if c > REGRESSION_THRESHOLD =>
+                            format!("  {:+.1}% REGRESSION", c * 100.0),
+                        Some(c) => format!("  {:+.1}%", c * 100.0),
+                        None => String::new(),
+                    }
+                );
+                let record = json!({
+                    "commit": commit,
+                    "timestamp": timestamp,
+                    "name": name,
+                    "day": solution.day,
+                    "part": part,
+                    "input": input_name,
+                    "input_bytes": inp.len(),
+                    "samples": measurement.samples.len(),
+                    "min_ns": measurement.min_ns() as u64,
+                    "median_ns": measurement.median_ns() as u64,
+                    "mean_ns": measurement.mean_ns() as u64,
+                });
+                writeln!(history_file, "{}", record).unwrap();
+            }
+        }
+    }
+}
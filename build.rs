@@ -0,0 +1,29 @@
+//! Generates the C header from `src/capi.rs` with the `capi` feature, into `OUT_DIR` so
+//! that building never touches the tree. `include/aoc2023.h` is a copy of it, which
+//! `tests/capi.rs` checks is up to date.
+
+fn main() {
+    #[cfg(feature = "capi")]
+    {
+        println!("cargo:rerun-if-changed=src/capi.rs");
+        let config = cbindgen::Config {
+            language: cbindgen::Language::C,
+            include_guard: Some("AOC2023_H".to_owned()),
+            header: Some(
+                "/* The C API of the aoc2023 cdylib. Generated from src/capi.rs by build.rs, so \
+                 don't edit it. */"
+                    .to_owned(),
+            ),
+            cpp_compat: true,
+            usize_is_size_t: true,
+            ..Default::default()
+        };
+        let out_dir = std::env::var("OUT_DIR").unwrap();
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src("src/capi.rs")
+            .generate()
+            .expect("Couldn't generate the C header")
+            .write_to_file(std::path::Path::new(&out_dir).join("aoc2023.h"));
+    }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc2023::{day1_report, Day1Decoder, Day1NoDigitsPolicy};
+use libfuzzer_sys::fuzz_target;
+
+// Day 1 has no parser as such, so decode every line with both scanners instead
+fuzz_target!(|data: &[u8]| {
+    let inp = String::from_utf8_lossy(data);
+    for with_words in [false, true] {
+        let _ = day1_report(&inp, with_words, Day1NoDigitsPolicy::Error);
+    }
+    let _ = Day1Decoder::english().report(&inp, Day1NoDigitsPolicy::Error);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any input has to come back as Ok or Err, never a panic, and part 1 has to solve any
+// almanac that parses. Part 2 isn't run since the seed ranges can be any length.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(almanac) = aoc2023::day5_parse(&String::from_utf8_lossy(data)) {
+        aoc2023::day5_solve_part1(&almanac);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any input has to come back as Ok or Err, never a panic
+fuzz_target!(|data: &[u8]| {
+    let _ = aoc2023::day6_parse(&String::from_utf8_lossy(data));
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any input has to come back as Ok or Err, never a panic, and any network that parses has
+// to be walkable from each of its nodes. The solvers aren't run since they needn't ever
+// reach the end.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(network) = aoc2023::day8_parse(&String::from_utf8_lossy(data)) {
+        for node in network.nodes.keys() {
+            assert!(network.walk(node, 100).is_some());
+        }
+    }
+});
@@ -0,0 +1,58 @@
+/* The C API of the aoc2023 cdylib. Generated from src/capi.rs by build.rs, so don't edit it. */
+
+#ifndef AOC2023_H
+#define AOC2023_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Copy the registered days into `days`, up to `capacity` of them, and return how many
+ * there are in total.
+ *
+ * # Safety
+ *
+ * `days` must point to at least `capacity` writable bytes, or be null if `capacity` is 0.
+ */
+size_t aoc_days(uint8_t *days, size_t capacity);
+
+/**
+ * Solve `part` of `day` for the `input_len` bytes of UTF-8 at `input`.
+ *
+ * Returns 0 and sets `*answer` to the answer, or returns -1 and sets `*error` to why
+ * not, e.g. the input didn't parse. Whichever is set must be freed with
+ * `aoc_free_string`; the other is set to null.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes, or be null if `input_len` is 0.
+ * `answer` and `error` must be valid to write a pointer to.
+ */
+int aoc_solve(uint8_t day,
+              uint8_t part,
+              const char *input,
+              size_t input_len,
+              char **answer,
+              char **error);
+
+/**
+ * Free a string returned by this library. Null is ignored.
+ *
+ * # Safety
+ *
+ * `s` must be null or a string from this library that hasn't been freed yet.
+ */
+void aoc_free_string(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC2023_H */
@@ -0,0 +1,86 @@
+//! A C API for the solutions, declared in `include/aoc2023.h`. Strings returned to the
+//! caller are allocated here and must be given back to `aoc_free_string`.
+
+use std::{
+    ffi::{c_char, c_int, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+};
+
+use crate::{find_solution, SOLUTIONS};
+
+/// Hand a string to the caller, with any NULs escaped since C can't represent them.
+fn to_c_string(s: &str) -> *mut c_char {
+    CString::new(s.replace('\0', "\\0")).unwrap().into_raw()
+}
+
+/// Copy the registered days into `days`, up to `capacity` of them, and return how many
+/// there are in total.
+///
+/// # Safety
+///
+/// `days` must point to at least `capacity` writable bytes, or be null if `capacity` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_days(days: *mut u8, capacity: usize) -> usize {
+    for (i, solution) in SOLUTIONS.iter().take(capacity).enumerate() {
+        *days.add(i) = solution.day;
+    }
+    SOLUTIONS.len()
+}
+
+/// Solve `part` of `day` for the `input_len` bytes of UTF-8 at `input`.
+///
+/// Returns 0 and sets `*answer` to the answer, or returns -1 and sets `*error` to why
+/// not, e.g. the input didn't parse. Whichever is set must be freed with
+/// `aoc_free_string`; the other is set to null.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, or be null if `input_len` is 0.
+/// `answer` and `error` must be valid to write a pointer to.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const c_char,
+    input_len: usize,
+    answer: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> c_int {
+    let bytes = match input_len {
+        0 => &[][..],
+        _ => slice::from_raw_parts(input.cast::<u8>(), input_len),
+    };
+    let result = str::from_utf8(bytes)
+        .map_err(|e| format!("Input isn't UTF-8: {}", e))
+        .and_then(|inp| {
+            let solution = find_solution(day).ok_or(format!("No solution for day {}", day))?;
+            // Unwinding into C is undefined behaviour
+            panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, inp)))
+                .unwrap_or(Err("The solution panicked".to_owned()))
+        });
+    match result {
+        Ok(solved) => {
+            *answer = to_c_string(&solved);
+            *error = ptr::null_mut();
+            0
+        }
+        Err(e) => {
+            *answer = ptr::null_mut();
+            *error = to_c_string(&e);
+            -1
+        }
+    }
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string from this library that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
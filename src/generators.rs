@@ -0,0 +1,836 @@
+//! Seeded generators for synthetic puzzle inputs. The same seed and options always
+//! produce the same input, so generated inputs can be used for benchmarks and tests
+//! without being committed.
+
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use crate::DAY1_ENGLISH_WORDS;
+
+/// An error unless `p` is a probability.
+fn check_probability(name: &str, p: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&p) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 0 and 1, got {}", name, p))
+    }
+}
+
+/// A small SplitMix64 generator. It's implemented here rather than taken from a crate
+/// so that generated inputs stay the same across dependency upgrades.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must be non-zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn range_i64(&mut self, range: RangeInclusive<i64>) -> i64 {
+        range.start() + self.below(range.end().abs_diff(*range.start()) + 1) as i64
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Day1Options {
+    pub num_lines: usize,
+    /// Probability that a character position holds a spelled-out digit.
+    pub word_density: f64,
+}
+
+impl Default for Day1Options {
+    fn default() -> Self {
+        Day1Options {
+            num_lines: 1000,
+            word_density: 0.1,
+        }
+    }
+}
+
+/// Calibration lines of letters, digits and digit words. Every line has at least one
+/// plain digit, so both parts are well-defined.
+pub fn day1(seed: u64, options: &Day1Options) -> Result<String, String> {
+    check_probability("The word density", options.word_density)?;
+    let mut rng = Rng::new(seed);
+    let mut inp = String::new();
+    for _ in 0..options.num_lines {
+        let line_len = rng.range(4..=40) as usize;
+        let digit_pos = rng.below(line_len as u64) as usize;
+        for i in 0..line_len {
+            if i == digit_pos || rng.chance(0.1) {
+                inp.push(char::from(b'0' + rng.below(10) as u8));
+            } else if rng.chance(options.word_density) {
+                inp.push_str(rng.pick(&DAY1_ENGLISH_WORDS).0);
+            } else {
+                inp.push(char::from(b'a' + rng.below(26) as u8));
+            }
+        }
+        inp.push('\n');
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day2Options {
+    pub num_games: usize,
+    pub max_draws: usize,
+    pub max_cubes: u64,
+}
+
+impl Default for Day2Options {
+    fn default() -> Self {
+        Day2Options {
+            num_games: 100,
+            max_draws: 6,
+            max_cubes: 20,
+        }
+    }
+}
+
+pub fn day2(seed: u64, options: &Day2Options) -> Result<String, String> {
+    if options.max_draws == 0 || options.max_cubes == 0 {
+        return Err("Games need at least one draw of at least one cube".to_owned());
+    }
+    let mut rng = Rng::new(seed);
+    let mut inp = String::new();
+    for id in 1..=options.num_games {
+        let num_of_draws = rng.range(1..=options.max_draws as u64);
+        let draws = (0..num_of_draws)
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+                let num_of_colours = rng.range(1..=3) as usize;
+                colours[..num_of_colours]
+                    .iter()
+                    .map(|colour| format!("{} {}", rng.range(1..=options.max_cubes), colour))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        inp.push_str(&format!("Game {}: {}\n", id, draws.join("; ")));
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day3Options {
+    pub rows: usize,
+    pub cols: usize,
+    /// Probability that a number starts at a free cell.
+    pub number_density: f64,
+    /// Probability that a free cell holds a symbol.
+    pub symbol_density: f64,
+    /// Fraction of symbols that are `*`, i.e. potential gears.
+    pub gear_density: f64,
+}
+
+impl Default for Day3Options {
+    fn default() -> Self {
+        Day3Options {
+            rows: 140,
+            cols: 140,
+            number_density: 0.1,
+            symbol_density: 0.05,
+            gear_density: 0.3,
+        }
+    }
+}
+
+/// An engine schematic of uniform width, with numbers of 1-3 digits that never touch
+/// each other horizontally.
+pub fn day3(seed: u64, options: &Day3Options) -> Result<String, String> {
+    if options.rows == 0 || options.cols == 0 {
+        return Err("The schematic needs at least one row and column".to_owned());
+    }
+    check_probability("The number density", options.number_density)?;
+    check_probability("The symbol density", options.symbol_density)?;
+    check_probability("The gear density", options.gear_density)?;
+    let mut rng = Rng::new(seed);
+    let symbols = ['#', '$', '%', '&', '+', '-', '/', '=', '@'];
+    let mut inp = String::new();
+    for _ in 0..options.rows {
+        let mut col = 0;
+        while col < options.cols {
+            let prev_is_digit = inp.ends_with(|c: char| c.is_ascii_digit());
+            if !prev_is_digit && rng.chance(options.number_density) {
+                let len = (rng.range(1..=3) as usize).min(options.cols - col);
+                let num = rng.range(10u64.pow(len as u32 - 1)..=10u64.pow(len as u32) - 1);
+                inp.push_str(&num.to_string());
+                col += len;
+                continue;
+            }
+            if rng.chance(options.symbol_density) {
+                if rng.chance(options.gear_density) {
+                    inp.push('*');
+                } else {
+                    inp.push(*rng.pick(&symbols));
+                }
+            } else {
+                inp.push('.');
+            }
+            col += 1;
+        }
+        inp.push('\n');
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day4Options {
+    pub num_cards: usize,
+    pub num_winning: usize,
+    pub num_you_have: usize,
+    pub max_number: u64,
+}
+
+impl Default for Day4Options {
+    fn default() -> Self {
+        Day4Options {
+            num_cards: 200,
+            num_winning: 10,
+            num_you_have: 25,
+            max_number: 99,
+        }
+    }
+}
+
+/// Scratchcards with distinct numbers on each side and no copies won past the last
+/// card, i.e. inputs that pass `day4_validate` in strict mode.
+pub fn day4(seed: u64, options: &Day4Options) -> Result<String, String> {
+    if options.num_winning + options.num_you_have > options.max_number as usize {
+        return Err(format!(
+            "A card needs {} distinct numbers but there are only {}",
+            options.num_winning + options.num_you_have,
+            options.max_number
+        ));
+    }
+    let mut rng = Rng::new(seed);
+    let mut inp = String::new();
+    let mut numbers = (1..=options.max_number).collect::<Vec<_>>();
+    let width = options.max_number.to_string().len();
+    let id_width = options.num_cards.to_string().len();
+    let format_nums = |nums: &[u64]| {
+        nums.iter()
+            .map(|n| format!("{:>width$}", n, width = width))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    for id in 1..=options.num_cards {
+        rng.shuffle(&mut numbers);
+        let max_matches = options
+            .num_winning
+            .min(options.num_you_have)
+            .min(options.num_cards - id);
+        let num_of_matches = rng.range(0..=max_matches as u64) as usize;
+        let winning = &numbers[..options.num_winning];
+        // The matches come from the winning numbers, the rest from numbers that aren't winning
+        let mut you_have = winning[..num_of_matches].to_vec();
+        you_have.extend(
+            &numbers
+                [options.num_winning..options.num_winning + options.num_you_have - num_of_matches],
+        );
+        rng.shuffle(&mut you_have);
+        inp.push_str(&format!(
+            "Card {:>id_width$}: {} | {}\n",
+            id,
+            format_nums(winning),
+            format_nums(&you_have),
+            id_width = id_width
+        ));
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day5Options {
+    pub num_seed_ranges: usize,
+    /// Longest seed range. Part 2 visits every seed, so keep this small.
+    pub max_seed_range_len: u64,
+    pub ranges_per_map: usize,
+    /// All seeds and map ranges lie below this.
+    pub max_value: u64,
+}
+
+impl Default for Day5Options {
+    fn default() -> Self {
+        Day5Options {
+            num_seed_ranges: 10,
+            max_seed_range_len: 1000,
+            ranges_per_map: 30,
+            max_value: 1 << 32,
+        }
+    }
+}
+
+pub const DAY5_MAP_NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// An almanac whose maps each have non-overlapping source ranges.
+pub fn day5(seed: u64, options: &Day5Options) -> Result<String, String> {
+    if options.num_seed_ranges == 0 || options.max_seed_range_len == 0 {
+        return Err("The almanac needs at least one non-empty seed range".to_owned());
+    }
+    if options.max_seed_range_len >= options.max_value {
+        return Err("Seed ranges must be shorter than the largest value".to_owned());
+    }
+    let mut rng = Rng::new(seed);
+    let seeds = (0..options.num_seed_ranges)
+        .map(|_| {
+            let len = rng.range(1..=options.max_seed_range_len);
+            format!("{} {}", rng.below(options.max_value - len), len)
+        })
+        .collect::<Vec<_>>();
+    let mut inp = format!("seeds: {}\n", seeds.join(" "));
+    for name in DAY5_MAP_NAMES {
+        inp.push_str(&format!("\n{} map:\n", name));
+        // Cut 0..max_value at sorted points and keep every other piece as a source range
+        let mut cuts = (0..options.ranges_per_map * 2)
+            .map(|_| rng.below(options.max_value))
+            .collect::<Vec<_>>();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut ranges = cuts
+            .chunks_exact(2)
+            .map(|pair| {
+                let (source_start, len) = (pair[0], pair[1] - pair[0]);
+                (rng.below(options.max_value - len), source_start, len)
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut ranges);
+        for (dest_start, source_start, len) in ranges {
+            inp.push_str(&format!("{} {} {}\n", dest_start, source_start, len));
+        }
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day6Options {
+    pub num_races: usize,
+    /// Number of digits in each race time.
+    pub time_digits: u32,
+    /// Fraction of races whose record is at least the best distance possible, so they
+    /// can't be won.
+    pub unwinnable_fraction: f64,
+    /// Fraction of races whose record is a distance that one of the hold times goes
+    /// exactly, so that hold time only ties it.
+    pub tie_fraction: f64,
+}
+
+impl Default for Day6Options {
+    fn default() -> Self {
+        Day6Options {
+            num_races: 4,
+            time_digits: 2,
+            unwinnable_fraction: 0.0,
+            tie_fraction: 0.0,
+        }
+    }
+}
+
+/// Races that can be won, apart from the chosen fraction that can't, some of which tie the
+/// record. The distances are kept short enough that the race made by concatenating all
+/// the numbers (part 2) can be won too if there's more than one race. Part 2 needs the
+/// concatenated time to fit in an `f64` exactly, i.e. at most 15 digits in total, and the
+/// concatenated distance to fit in a `u64`, so options that allow more are an error.
+pub fn day6(seed: u64, options: &Day6Options) -> Result<String, String> {
+    if options.num_races == 0 || options.time_digits < 2 {
+        return Err("Need at least one race, with times of at least 2 digits".to_owned());
+    }
+    let num_races = options.num_races as u32;
+    if num_races * options.time_digits > 15 || num_races * (2 * options.time_digits - 2) > 19 {
+        return Err(format!(
+            "{} races with {}-digit times are too long to join up for part 2",
+            options.num_races, options.time_digits
+        ));
+    }
+    check_probability("The unwinnable fraction", options.unwinnable_fraction)?;
+    check_probability("The tie fraction", options.tie_fraction)?;
+    let mut rng = Rng::new(seed);
+    let max_distance = 10u64.pow(2 * options.time_digits - 2) - 1;
+    let min_time = 10u64.pow(options.time_digits - 1);
+    // Holding for time / 2 goes the furthest, (time / 2) * (time - time / 2)
+    let best = |time: u64| (time / 2) * (time - time / 2);
+    let (times, distances): (Vec<u64>, Vec<u64>) = (0..options.num_races)
+        .map(|_| {
+            if rng.chance(options.unwinnable_fraction) {
+                // Times below 2 * min_time have a best distance of at most max_distance
+                let time = rng.range(min_time..=2 * min_time - 1);
+                (time, rng.range(best(time)..=max_distance))
+            } else if rng.chance(options.tie_fraction) {
+                let time = rng.range(min_time..=2 * min_time - 1);
+                let hold_time = rng.range(1..=time / 2);
+                (time, hold_time * (time - hold_time))
+            } else {
+                let time = rng.range(min_time..=10 * min_time - 1);
+                (time, rng.range(1..=(best(time) - 1).min(max_distance)))
+            }
+        })
+        .unzip();
+    let width = (2 * options.time_digits) as usize;
+    let format_nums = |nums: &[u64]| {
+        nums.iter()
+            .map(|n| format!("{:>width$}", n, width = width))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    Ok(format!(
+        "Time:     {}\nDistance: {}\n",
+        format_nums(&times),
+        format_nums(&distances)
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub struct Day7Options {
+    pub num_hands: usize,
+    /// Fraction of hands that are one pair, so many hands tie on type and have to be
+    /// ordered card by card.
+    pub tie_fraction: f64,
+    /// Probability of each card being a joker (`J`).
+    pub joker_density: f64,
+}
+
+impl Default for Day7Options {
+    fn default() -> Self {
+        Day7Options {
+            num_hands: 1000,
+            tie_fraction: 0.5,
+            joker_density: 1.0 / 13.0,
+        }
+    }
+}
+
+const DAY7_LABELS: &[u8; 12] = b"23456789TQKA";
+
+/// How many distinct hands `day7` can make with these options: hands of any cards, hands
+/// without jokers or just `JJJJJ` for the random ones, plus the one pair hands, which
+/// never have jokers.
+fn day7_num_possible_hands(options: &Day7Options) -> usize {
+    let num_labels = DAY7_LABELS.len();
+    let one_pair = num_labels * (num_labels - 1) * (num_labels - 2) * (num_labels - 3) / 6 * 60;
+    let random = match options.joker_density {
+        p if p <= 0.0 => num_labels.pow(5),
+        p if p >= 1.0 => 1,
+        _ => (num_labels + 1).pow(5),
+    };
+    match options.tie_fraction {
+        p if p <= 0.0 => random,
+        p if p >= 1.0 => one_pair,
+        _ if options.joker_density >= 1.0 => random + one_pair,
+        _ => random,
+    }
+}
+
+/// Distinct hands with random bids.
+pub fn day7(seed: u64, options: &Day7Options) -> Result<String, String> {
+    check_probability("The tie fraction", options.tie_fraction)?;
+    check_probability("The joker density", options.joker_density)?;
+    let num_possible_hands = day7_num_possible_hands(options);
+    if options.num_hands > num_possible_hands {
+        return Err(format!(
+            "Can't make {} distinct hands, only {}",
+            options.num_hands, num_possible_hands
+        ));
+    }
+    let mut rng = Rng::new(seed);
+    let labels = DAY7_LABELS;
+    let mut seen = HashSet::new();
+    let mut inp = String::new();
+    let random_card = |rng: &mut Rng| {
+        if rng.chance(options.joker_density) {
+            b'J'
+        } else {
+            *rng.pick(labels)
+        }
+    };
+    while seen.len() < options.num_hands {
+        let hand = if rng.chance(options.tie_fraction) {
+            // A pair plus three other distinct non-joker labels
+            let mut distinct = labels.to_vec();
+            rng.shuffle(&mut distinct);
+            let mut hand = vec![
+                distinct[0],
+                distinct[0],
+                distinct[1],
+                distinct[2],
+                distinct[3],
+            ];
+            rng.shuffle(&mut hand);
+            hand
+        } else {
+            (0..5).map(|_| random_card(&mut rng)).collect()
+        };
+        let hand = String::from_utf8(hand).unwrap();
+        if seen.insert(hand.clone()) {
+            inp.push_str(&format!("{} {}\n", hand, rng.range(1..=1000)));
+        }
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day8Options {
+    pub num_instructions: usize,
+    /// One entry per ghost: how many passes through the instructions it takes to get
+    /// from its `..A` node to its `..Z` node, and again from there back to the same
+    /// `..Z` node. The first ghost starts at `AAA` and ends at `ZZZ`.
+    pub cycle_lengths: Vec<usize>,
+}
+
+impl Default for Day8Options {
+    fn default() -> Self {
+        Day8Options {
+            num_instructions: 100,
+            cycle_lengths: vec![2, 3, 5, 7, 11, 13],
+        }
+    }
+}
+
+/// A network where each ghost follows its own loop of the chosen length. The direction
+/// not taken at each node points to a random node, so the instructions matter.
+pub fn day8(seed: u64, options: &Day8Options) -> Result<String, String> {
+    if options.cycle_lengths.is_empty() || options.cycle_lengths.contains(&0) {
+        return Err("Need at least one ghost, with non-zero cycle lengths".to_owned());
+    }
+    let mut rng = Rng::new(seed);
+    let instructions = (0..options.num_instructions.max(1))
+        .map(|_| *rng.pick(b"LR"))
+        .collect::<Vec<_>>();
+    let num_of_inner_nodes = options
+        .cycle_lengths
+        .iter()
+        .map(|c| c * instructions.len() - 1)
+        .sum::<usize>();
+    // Names are built without A or Z, so only the ghosts' start and end nodes (which get
+    // an A or Z appended) end with either, and made long enough to be unique
+    let alphabet = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+    let mut width = 3;
+    while alphabet.len().pow(width) < num_of_inner_nodes + options.cycle_lengths.len() {
+        width += 1;
+    }
+    let mut name_ids = (0..alphabet.len().pow(width)).collect::<Vec<_>>();
+    rng.shuffle(&mut name_ids);
+    let mut name_ids = name_ids.into_iter();
+    let mut new_name = || {
+        let mut id = name_ids.next().unwrap();
+        (0..width)
+            .map(|_| {
+                let c = alphabet[id % alphabet.len()];
+                id /= alphabet.len();
+                char::from(c)
+            })
+            .collect::<String>()
+    };
+
+    let mut nodes: Vec<(String, [usize; 2])> = Vec::new();
+    let mut loose_ends = Vec::new();
+    for (ghost, &cycle_len) in options.cycle_lengths.iter().enumerate() {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_owned(), "ZZZ".to_owned())
+        } else {
+            let prefix = new_name();
+            (format!("{}A", prefix), format!("{}Z", prefix))
+        };
+        // start -> n_1 -> ... -> n_(len-1) -> end -> n_1
+        let len = cycle_len * instructions.len();
+        let first = nodes.len();
+        nodes.push((start, [0, 0]));
+        for _ in 1..len {
+            nodes.push((new_name(), [0, 0]));
+        }
+        nodes.push((end, [0, 0]));
+        for step in 0..=len {
+            let node = first + step;
+            let next = if step == len { first + 1 } else { node + 1 };
+            let dir = usize::from(instructions[step % instructions.len()] == b'R');
+            nodes[node].1[dir] = next;
+            loose_ends.push((node, 1 - dir));
+        }
+    }
+    for (node, dir) in loose_ends {
+        nodes[node].1[dir] = rng.below(nodes.len() as u64) as usize;
+    }
+    let mut order = (0..nodes.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+    let mut inp = format!("{}\n\n", String::from_utf8(instructions).unwrap());
+    for node in order {
+        let (name, [left, right]) = &nodes[node];
+        inp.push_str(&format!(
+            "{} = ({}, {})\n",
+            name, nodes[*left].0, nodes[*right].0
+        ));
+    }
+    Ok(inp)
+}
+
+#[derive(Debug, Clone)]
+pub struct Day9Options {
+    pub num_lines: usize,
+    pub seq_len: usize,
+    /// Highest degree of the polynomial behind each line. Must be less than `seq_len - 1`,
+    /// and small enough for the values and their differences to fit in an `i64` (up to
+    /// 10 is fine for sequences of up to 21 values).
+    pub max_degree: usize,
+}
+
+impl Default for Day9Options {
+    fn default() -> Self {
+        Day9Options {
+            num_lines: 200,
+            seq_len: 21,
+            max_degree: 6,
+        }
+    }
+}
+
+const DAY9_MAX_COEFFICIENT: i64 = 5;
+
+/// Whether every value of a polynomial of up to `max_degree` with coefficients up to
+/// `DAY9_MAX_COEFFICIENT`, from one before the sequence to one after it, fits in an
+/// `i64` along with all of the differences between them. Differences at each level are
+/// at most twice the ones at the level above.
+fn day9_fits(options: &Day9Options) -> bool {
+    let x = options.seq_len as i128;
+    let max_value = (0..=options.max_degree as u32)
+        .try_fold(0i128, |sum, k| sum.checked_add(x.checked_pow(k)?))
+        .and_then(|sum| sum.checked_mul(DAY9_MAX_COEFFICIENT.into()));
+    max_value
+        .and_then(|max_value| max_value.checked_mul(1i128.checked_shl(options.max_degree as u32)?))
+        .is_some_and(|max_difference| max_difference <= i64::MAX.into())
+}
+
+/// Lines of integer polynomials with small coefficients, evaluated at `0..seq_len`.
+pub fn day9(seed: u64, options: &Day9Options) -> Result<String, String> {
+    if options.max_degree + 1 >= options.seq_len {
+        return Err("Sequences must be longer than the degree + 1".to_owned());
+    }
+    if !day9_fits(options) {
+        return Err(format!(
+            "Polynomials of degree {} over {} values can overflow an i64",
+            options.max_degree, options.seq_len
+        ));
+    }
+    let mut rng = Rng::new(seed);
+    let mut inp = String::new();
+    for _ in 0..options.num_lines {
+        let degree = rng.range(0..=options.max_degree as u64) as usize;
+        let coefficients = (0..=degree)
+            .map(|_| rng.range_i64(-DAY9_MAX_COEFFICIENT..=DAY9_MAX_COEFFICIENT))
+            .collect::<Vec<_>>();
+        let values = (0..options.seq_len as i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, coefficient| acc * x + coefficient)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        inp.push_str(&values.join(" "));
+        inp.push('\n');
+    }
+    Ok(inp)
+}
+
+/// An input for `day` with the default options, scaled by `size`: lines, games, cards,
+/// hands or races for most days, the side of the grid for day 3, the number of seed
+/// ranges for day 5 and the number of instructions for day 8.
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String, String> {
+    match day {
+        1 => day1(
+            seed,
+            &Day1Options {
+                num_lines: size,
+                ..Default::default()
+            },
+        ),
+        2 => day2(
+            seed,
+            &Day2Options {
+                num_games: size,
+                ..Default::default()
+            },
+        ),
+        3 => day3(
+            seed,
+            &Day3Options {
+                rows: size,
+                cols: size,
+                ..Default::default()
+            },
+        ),
+        4 => day4(
+            seed,
+            &Day4Options {
+                num_cards: size,
+                ..Default::default()
+            },
+        ),
+        5 => day5(
+            seed,
+            &Day5Options {
+                num_seed_ranges: size,
+                ..Default::default()
+            },
+        ),
+        6 => day6(
+            seed,
+            &Day6Options {
+                num_races: size,
+                ..Default::default()
+            },
+        ),
+        7 => day7(
+            seed,
+            &Day7Options {
+                num_hands: size,
+                ..Default::default()
+            },
+        ),
+        8 => day8(
+            seed,
+            &Day8Options {
+                num_instructions: size,
+                ..Default::default()
+            },
+        ),
+        9 => day9(
+            seed,
+            &Day9Options {
+                num_lines: size,
+                ..Default::default()
+            },
+        ),
+        _ => Err(format!("No generator for day {}", day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_generators_are_reproducible() {
+        for day in 1..=9 {
+            let inp = generate(day, 42, 5).unwrap();
+            assert_eq!(generate(day, 42, 5), Ok(inp.clone()));
+            assert_ne!(generate(day, 43, 5), Ok(inp));
+        }
+        assert_eq!(
+            generate(10, 42, 20),
+            Err("No generator for day 10".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_bad_options_are_errors() {
+        assert!(generate(6, 0, 12).is_err());
+        assert!(generate(5, 0, 0).is_err());
+        assert!(generate(3, 0, 0).is_err());
+        let options = Day8Options {
+            num_instructions: 10,
+            cycle_lengths: vec![0],
+        };
+        assert!(day8(0, &options).is_err());
+        let options = Day7Options {
+            num_hands: 2,
+            joker_density: 1.0,
+            tie_fraction: 0.0,
+        };
+        assert!(day7(0, &options).is_err());
+        assert!(day7(
+            0,
+            &Day7Options {
+                num_hands: 1,
+                ..options
+            }
+        )
+        .is_ok());
+        let options = Day9Options {
+            num_lines: 1,
+            seq_len: 20,
+            max_degree: 18,
+        };
+        assert!(day9(0, &options).is_err());
+        let options = Day9Options {
+            seq_len: 21,
+            max_degree: 10,
+            ..options
+        };
+        assert!(day9(0, &options).is_ok());
+    }
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for solution in SOLUTIONS.iter() {
+            // Day 6's part 2 race joins up all the races' numbers, which only fit in a
+            // `u64` with a few races
+            let size = if solution.day == 6 { 3 } else { 20 };
+            let inp = match generate(solution.day, 7, size) {
+                Ok(inp) => inp,
+                // Newly added days don't have generators yet
+                Err(e) if e.starts_with("No generator") => continue,
+                Err(e) => panic!("Day {}: {}", solution.day, e),
+            };
+            for part in 1..=2 {
+                solution.solve(part, &inp).unwrap();
+            }
+        }
+        let inp = day4(7, &Day4Options::default()).unwrap();
+        assert_eq!(day4_validate(&inp, Day4ValidationMode::Strict), Ok(vec![]));
+        let inp = day2(7, &Day2Options::default()).unwrap();
+        assert_eq!(day2_check_ids(&day2_parse(&inp).unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_day8_cycles() {
+        let options = Day8Options {
+            num_instructions: 7,
+            cycle_lengths: vec![2, 3, 5],
+        };
+        let inp = day8(1, &options).unwrap();
+        assert_eq!(day8_part1(&inp), Ok(2 * 7));
+        assert_eq!(day8_part2(&inp), Ok(2 * 3 * 5 * 7));
+    }
+}
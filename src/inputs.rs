@@ -0,0 +1,190 @@
+//! Where puzzle inputs come from: files under `inputs/<profile>/`, fetched and saved there
+//! the first time they're needed.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ureq::Agent;
+
+use crate::{
+    read_input,
+    report::{parse_answers, Answers},
+};
+
+pub const YEAR: u16 = 2023;
+
+/// The profile the committed inputs are under.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The names of the profiles under `root`, i.e. its subdirectories, in order.
+pub fn profiles(root: impl AsRef<Path>) -> Result<Vec<String>, String> {
+    let root = root.as_ref();
+    let mut profiles = Vec::new();
+    for entry in fs::read_dir(root).map_err(|e| format!("{}: {}", root.display(), e))? {
+        let entry = entry.map_err(|e| format!("{}: {}", root.display(), e))?;
+        if entry.path().is_dir() {
+            profiles.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+/// Gets the input for a day from somewhere other than the store.
+pub trait Fetcher {
+    fn fetch(&self, day: u8) -> Result<String, String>;
+}
+
+/// Downloads inputs from the Advent of Code site, logged in with a session cookie.
+pub struct HttpFetcher {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl HttpFetcher {
+    pub fn new(session: &str) -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL, session)
+    }
+
+    /// Fetch from another server, e.g. a mock one in tests.
+    pub fn with_base_url(base_url: &str, session: &str) -> Self {
+        HttpFetcher {
+            agent: agent(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+        }
+    }
+}
+
+pub(crate) const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request, since the site asks automated tools to identify themselves.
+pub(crate) const USER_AGENT: &str = "aoc2023 solutions runner";
+
+pub(crate) fn agent() -> Agent {
+    Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(30)))
+        .build()
+        .into()
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        self.agent
+            .get(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| format!("{}: {}", url, e))
+    }
+}
+
+/// Copies inputs from a local directory of `dayN.txt` files, e.g. downloads saved by hand.
+pub struct DirFetcher {
+    dir: PathBuf,
+}
+
+impl DirFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        DirFetcher { dir: dir.into() }
+    }
+}
+
+impl Fetcher for DirFetcher {
+    fn fetch(&self, day: u8) -> Result<String, String> {
+        let path = self.dir.join(format!("day{}.txt", day));
+        fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Inputs stored as `<root>/<profile>/dayN.txt`. Missing inputs are fetched once and saved,
+/// so a fetcher is never asked for an input that's already stored. A part can have its own
+/// input in `dayN.partM.txt`, for examples that differ between parts; it's never fetched.
+pub struct InputStore {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl InputStore {
+    pub fn new(root: impl AsRef<Path>, profile: &str) -> Self {
+        InputStore {
+            dir: root.as_ref().join(profile),
+            fetcher: None,
+        }
+    }
+
+    /// Fetch missing inputs with `fetcher`. Without one, missing inputs are an error.
+    pub fn with_fetcher(mut self, fetcher: impl Fetcher + 'static) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.txt", day))
+    }
+
+    /// Where a part's own input would be, if it has one.
+    pub fn part_path(&self, day: u8, part: u8) -> PathBuf {
+        self.dir.join(format!("day{}.part{}.txt", day, part))
+    }
+
+    /// Whether there's a stored input for a day. Empty files, like the placeholders
+    /// `new-day` makes, don't count.
+    pub fn has(&self, day: u8) -> bool {
+        fs::metadata(self.path(day)).is_ok_and(|meta| meta.len() > 0)
+    }
+
+    /// The expected answers in `answers.txt` next to the inputs, if there is one.
+    pub fn answers(&self) -> Result<Answers, String> {
+        let path = self.dir.join("answers.txt");
+        match read_input(&path) {
+            Ok(answers) => parse_answers(&answers),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The normalised input for a day, fetching and saving it first if it's missing or
+    /// empty.
+    pub fn get(&self, day: u8) -> Result<String, String> {
+        let path = self.path(day);
+        match read_input(&path) {
+            Ok(inp) if !inp.is_empty() => return Ok(inp),
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        }
+        let fetcher = self.fetcher.as_ref().ok_or(format!(
+            "{} is missing or empty and there's nothing to fetch it with",
+            path.display()
+        ))?;
+        let inp = fetcher.fetch(day)?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, &inp))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        read_input(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The normalised input for a part of a day: its own input if it has a non-empty one,
+    /// or else the day's.
+    pub fn get_part(&self, day: u8, part: u8) -> Result<String, String> {
+        let path = self.part_path(day, part);
+        match read_input(&path) {
+            Ok(inp) if !inp.is_empty() => Ok(inp),
+            Ok(_) => self.get(day),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => self.get(day),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+}
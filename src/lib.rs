@@ -1,230 +1,572 @@
 use std::{
+    any::Any,
+    borrow::Cow,
     cmp::Ordering,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    fs, io,
     ops::Not,
+    path::Path,
     str::FromStr,
 };
 
+use aho_corasick::{AhoCorasick, Match};
+use cancel::{CancellationToken, Cancelled};
 use counter::Counter;
 use num::{BigInt, BigRational, FromPrimitive, Integer};
+use observer::{Observer, Silent};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 use regex::Regex;
 
-pub fn day1_part1(inp: &str) -> u32 {
-    return inp
-        .lines()
-        .map(|line: &str| {
-            let i = line.find(|c: char| c.is_numeric()).unwrap();
-            let j = line.rfind(|c: char| c.is_numeric()).unwrap();
-            let ii = line.chars().nth(i).unwrap().to_digit(10).unwrap();
-            let jj = line.chars().nth(j).unwrap().to_digit(10).unwrap();
-            ii * 10 + jj
+/// `into_par_iter` with the `parallel` feature and `into_iter` without, for loops whose
+/// iterations are independent. Only adaptors that both kinds of iterator have can follow.
+#[cfg(feature = "parallel")]
+macro_rules! maybe_par_iter {
+    ($e:expr) => {
+        rayon::iter::IntoParallelIterator::into_par_iter($e)
+    };
+}
+#[cfg(not(feature = "parallel"))]
+macro_rules! maybe_par_iter {
+    ($e:expr) => {
+        IntoIterator::into_iter($e)
+    };
+}
+
+pub mod cancel;
+#[cfg(feature = "capi")]
+mod capi;
+pub mod generators;
+pub mod inputs;
+pub mod observer;
+#[cfg(feature = "python")]
+mod python;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod submit;
+pub mod watch;
+
+/// Put an input in the form the parsers expect: no BOM, `\n` line endings, no whitespace
+/// at the end of lines, no blank lines at the end and exactly one final newline. Inputs
+/// saved on Windows or by some editors differ from the downloaded ones in these ways.
+///
+/// Borrows the input when it's already in that form.
+pub fn normalise_input(inp: &str) -> Cow<'_, str> {
+    let inp = inp.strip_prefix('\u{feff}').unwrap_or(inp);
+    let mut normalised = String::with_capacity(inp.len() + 1);
+    for line in inp.trim_end().lines() {
+        normalised.push_str(line.trim_end());
+        normalised.push('\n');
+    }
+    if normalised == inp {
+        Cow::Borrowed(inp)
+    } else {
+        Cow::Owned(normalised)
+    }
+}
+
+/// Read an input file and normalise it with `normalise_input`.
+pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
+    fs::read_to_string(path).map(|inp| normalise_input(&inp).into_owned())
+}
+
+/// The plain digits `0`-`9`.
+pub const DAY1_DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// The spelled-out English digits used in part 2.
+pub const DAY1_ENGLISH_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A digit token matched in a calibration line, with its byte range in the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Day1Token {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+/// What to do with a calibration line that has no digits in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day1NoDigitsPolicy {
+    /// Leave the line out of the report (it adds nothing to the sum).
+    Skip,
+    /// Report the line with a calibration value of 0.
+    CountAsZero,
+    /// Fail with the line number.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day1LineReport {
+    /// 1-based line number.
+    pub line: usize,
+    /// The first and last tokens, or `None` if the line has no digits.
+    pub tokens: Option<(Day1Token, Day1Token)>,
+    pub value: u32,
+}
+
+/// The digit token starting at byte `i`, if any. Only ASCII digits count unless
+/// `with_words` is set, in which case the English words do too.
+fn day1_token_at(line: &[u8], i: usize, with_words: bool) -> Option<Day1Token> {
+    let b = line[i];
+    if b.is_ascii_digit() {
+        return Some(Day1Token {
+            start: i,
+            end: i + 1,
+            value: (b - b'0').into(),
+        });
+    }
+    if !with_words {
+        return None;
+    }
+    DAY1_ENGLISH_WORDS
+        .iter()
+        .find(|(word, _)| line[i..].starts_with(word.as_bytes()))
+        .map(|&(word, value)| Day1Token {
+            start: i,
+            end: i + word.len(),
+            value,
         })
-        .sum();
 }
 
-pub fn day1_part2(inp: &str) -> u32 {
-    let digits_map = [
-        ("0", 0),
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ];
-    return inp
-        .lines()
-        .map(|line: &str| {
-            let left_num = digits_map
-                .iter()
-                .map(|(s, n)| {
-                    let res = line.find(s).unwrap_or(usize::MAX);
-                    (res, n)
-                })
-                .min_by(|&(x, _), &(y, _)| x.cmp(&y))
-                .unwrap()
-                .1;
-            let right_num = digits_map
+/// The first digit token in the line, scanning forwards.
+pub fn day1_first_digit(line: &str, with_words: bool) -> Option<Day1Token> {
+    let bytes = line.as_bytes();
+    (0..bytes.len()).find_map(|i| day1_token_at(bytes, i, with_words))
+}
+
+/// The last digit token in the line, scanning backwards.
+pub fn day1_last_digit(line: &str, with_words: bool) -> Option<Day1Token> {
+    let bytes = line.as_bytes();
+    (0..bytes.len())
+        .rev()
+        .find_map(|i| day1_token_at(bytes, i, with_words))
+}
+
+fn day1_line_reports<'a, F>(
+    inp: &'a str,
+    policy: Day1NoDigitsPolicy,
+    find_tokens: F,
+) -> impl Iterator<Item = Result<Day1LineReport, String>> + 'a
+where
+    F: Fn(&str) -> Option<(Day1Token, Day1Token)> + 'a,
+{
+    inp.lines().enumerate().filter_map(move |(i, line)| {
+        let tokens = find_tokens(line);
+        let value = match (tokens, policy) {
+            (Some((first, last)), _) => first.value * 10 + last.value,
+            (None, Day1NoDigitsPolicy::Skip) => return None,
+            (None, Day1NoDigitsPolicy::CountAsZero) => 0,
+            (None, Day1NoDigitsPolicy::Error) => {
+                return Some(Err(format!("Line {} has no digits", i + 1)))
+            }
+        };
+        Some(Ok(Day1LineReport {
+            line: i + 1,
+            tokens,
+            value,
+        }))
+    })
+}
+
+fn day1_find_tokens(line: &str, with_words: bool) -> Option<(Day1Token, Day1Token)> {
+    Some((
+        day1_first_digit(line, with_words)?,
+        day1_last_digit(line, with_words)?,
+    ))
+}
+
+/// Each line's calibration value, along with the tokens it was decoded from.
+pub fn day1_report(
+    inp: &str,
+    with_words: bool,
+    policy: Day1NoDigitsPolicy,
+) -> Result<Vec<Day1LineReport>, String> {
+    let inp = &*normalise_input(inp);
+    day1_line_reports(inp, policy, |line| day1_find_tokens(line, with_words)).collect()
+}
+
+/// The sum of all calibration values.
+pub fn day1_sum(inp: &str, with_words: bool, policy: Day1NoDigitsPolicy) -> Result<u32, String> {
+    let inp = &*normalise_input(inp);
+    day1_line_reports(inp, policy, |line| day1_find_tokens(line, with_words))
+        .map(|report| report.map(|report| report.value))
+        .sum()
+}
+
+/// Day 1 has nothing to parse up front since each part reads the lines differently, so
+/// this just normalises the input. Each part checks that every line has a digit it can
+/// read, since a spelled-out digit only counts in part 2.
+pub fn day1_parse(inp: &str) -> Result<String, String> {
+    Ok(normalise_input(inp).into_owned())
+}
+
+/// The sum of the calibration values, or an error naming the first line without a digit.
+pub fn day1_part1(inp: &str) -> Result<u32, String> {
+    day1_sum(inp, false, Day1NoDigitsPolicy::Error)
+}
+
+/// Decodes calibration lines using an arbitrary vocabulary of tokens, e.g. words in
+/// another language or roman numerals.
+///
+/// All tokens are matched in a single Aho-Corasick pass over each line, including
+/// overlapping ones, so `twone` yields both `two` and `one`. The first token is the
+/// one starting earliest and the last token is the one ending latest; ties go to the
+/// longer token.
+pub struct Day1Decoder {
+    automaton: AhoCorasick,
+    values: Vec<u32>,
+}
+
+impl Day1Decoder {
+    pub fn new<S: AsRef<str>>(
+        vocabulary: impl IntoIterator<Item = (S, u32)>,
+    ) -> Result<Self, String> {
+        let (tokens, values): (Vec<S>, Vec<u32>) = vocabulary.into_iter().unzip();
+        if let Some(i) = tokens.iter().position(|token| token.as_ref().is_empty()) {
+            return Err(format!("Token for value {} is empty", values[i]));
+        }
+        let automaton = AhoCorasick::new(tokens.iter().map(|token| token.as_ref()))
+            .map_err(|e| e.to_string())?;
+        Ok(Day1Decoder { automaton, values })
+    }
+
+    /// The vocabulary for part 2: digits and the English words for them.
+    pub fn english() -> Self {
+        Day1Decoder::new(DAY1_DIGITS.into_iter().chain(DAY1_ENGLISH_WORDS)).unwrap()
+    }
+
+    fn to_token(&self, m: Match) -> Day1Token {
+        Day1Token {
+            start: m.start(),
+            end: m.end(),
+            value: self.values[m.pattern().as_usize()],
+        }
+    }
+
+    /// The first and last tokens in the line, if it has any tokens.
+    pub fn first_and_last(&self, line: &str) -> Option<(Day1Token, Day1Token)> {
+        let mut first: Option<Match> = None;
+        let mut last: Option<Match> = None;
+        for m in self.automaton.find_overlapping_iter(line) {
+            if first.is_none_or(|f| (m.start(), f.len()) < (f.start(), m.len())) {
+                first = Some(m);
+            }
+            if last.is_none_or(|l| (m.end(), m.len()) > (l.end(), l.len())) {
+                last = Some(m);
+            }
+        }
+        Some((self.to_token(first?), self.to_token(last?)))
+    }
+
+    /// The calibration value of a line, i.e. `first * 10 + last`.
+    pub fn decode_line(&self, line: &str) -> Option<u32> {
+        self.first_and_last(line)
+            .map(|(first, last)| first.value * 10 + last.value)
+    }
+
+    /// Each line's calibration value, along with the tokens it was decoded from.
+    pub fn report(
+        &self,
+        inp: &str,
+        policy: Day1NoDigitsPolicy,
+    ) -> Result<Vec<Day1LineReport>, String> {
+        let inp = &*normalise_input(inp);
+        day1_line_reports(inp, policy, |line| self.first_and_last(line)).collect()
+    }
+
+    /// The sum of the calibration values of all lines.
+    pub fn decode(&self, inp: &str, policy: Day1NoDigitsPolicy) -> Result<u32, String> {
+        let inp = &*normalise_input(inp);
+        day1_line_reports(inp, policy, |line| self.first_and_last(line))
+            .map(|report| report.map(|report| report.value))
+            .sum()
+    }
+}
+
+/// The sum of the calibration values, reading spelled-out digits too, or an error naming
+/// the first line without any digit.
+pub fn day1_part2(inp: &str) -> Result<u32, String> {
+    day1_sum(inp, true, Day1NoDigitsPolicy::Error)
+}
+
+/// One handful of cubes shown from the bag, e.g. `3 blue, 4 red`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day2Draw {
+    pub cubes: HashMap<String, u32>,
+}
+
+impl FromStr for Day2Draw {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cubes = HashMap::new();
+        for num_and_colour in s.split(", ") {
+            let (num_str, colour) = num_and_colour.split_once(' ').ok_or(format!(
+                "Expected `<num> <colour>`, got: {}",
+                num_and_colour
+            ))?;
+            let num = num_str
+                .parse::<u32>()
+                .map_err(|e| format!("Bad cube count {}: {}", num_str, e))?;
+            *cubes.entry(colour.to_owned()).or_insert(0) += num;
+        }
+        Ok(Day2Draw { cubes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day2Game {
+    pub id: usize,
+    pub draws: Vec<Day2Draw>,
+}
+
+impl FromStr for Day2Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (game_str, draws_str) = s.split_once(": ").ok_or("Parsing failed")?;
+        let id = game_str
+            .strip_prefix("Game ")
+            .ok_or(format!("Expected `Game <id>`, got: {}", game_str))?
+            .parse()
+            .map_err(|e| format!("Bad game id {}: {}", game_str, e))?;
+        let draws = draws_str
+            .split("; ")
+            .map(|draw_str| draw_str.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Day2Game { id, draws })
+    }
+}
+
+impl Day2Game {
+    /// Whether every draw fits in a bag holding at most `bag[colour]` cubes of each colour.
+    /// Colours missing from the bag have a limit of 0.
+    pub fn is_possible_with(&self, bag: &HashMap<String, u32>) -> bool {
+        self.draws.iter().all(|draw| {
+            draw.cubes
                 .iter()
-                .map(|(s, n)| {
-                    let res = line
-                        .rfind(s)
-                        .map(|x| i64::try_from(x).unwrap())
-                        .unwrap_or(i64::MIN);
-                    (res, n)
-                })
-                .max_by(|&(x, _), &(y, _)| x.cmp(&y))
-                .unwrap()
-                .1;
-            left_num * 10 + right_num
+                .all(|(colour, &num)| num <= bag.get(colour).copied().unwrap_or(0))
         })
-        .sum();
+    }
+
+    /// The fewest cubes of each colour seen in this game that the bag must have held.
+    pub fn minimal_bag(&self) -> HashMap<String, u32> {
+        let mut bag = HashMap::new();
+        for (colour, &num) in self.draws.iter().flat_map(|draw| draw.cubes.iter()) {
+            let max_num = bag.entry(colour.clone()).or_insert(0);
+            *max_num = num.max(*max_num);
+        }
+        bag
+    }
 }
 
-pub fn day2_part1(inp: &str) -> usize {
-    return inp
-        .lines()
+pub fn day2_parse(inp: &str) -> Result<Vec<Day2Game>, String> {
+    let inp = &*normalise_input(inp);
+    inp.lines()
         .enumerate()
-        .filter(|(_, line)| {
-            let i = line.find(": ").unwrap() + ": ".len();
-            return line[i..].split("; ").all(|sub_game| {
-                return sub_game.split(", ").all(|num_and_colour| {
-                    let num: u32 = num_and_colour.split(' ').next().unwrap().parse().unwrap();
-                    let colour = num_and_colour.split(' ').nth(1).unwrap();
-                    match colour {
-                        "red" => num <= 12,
-                        "green" => num <= 13,
-                        "blue" => num <= 14,
-                        other => panic!("Unexpected colour: {}", other),
-                    }
-                });
-            });
-        })
-        .map(|(i, _)| i + 1)
-        .sum();
+        .map(|(i, line)| line.parse().map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// The bag the elf asks about in part 1: 12 red, 13 green and 14 blue cubes.
+pub fn day2_part1_bag() -> HashMap<String, u32> {
+    [("red", 12), ("green", 13), ("blue", 14)]
+        .into_iter()
+        .map(|(colour, num)| (colour.to_owned(), num))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Day2IdIssue {
+    /// The same game id appears on more than one (1-based) line.
+    Duplicate { id: usize, lines: Vec<usize> },
+    /// No line has this id, although a larger id is present.
+    Missing { id: usize },
+}
+
+/// Check that the game ids are exactly `1..=max_id` with no repeats.
+/// `games` is expected in input order, so duplicates are reported by line number.
+pub fn day2_check_ids(games: &[Day2Game]) -> Vec<Day2IdIssue> {
+    let mut lines_by_id: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, game) in games.iter().enumerate() {
+        lines_by_id.entry(game.id).or_default().push(i + 1);
+    }
+    let max_id = lines_by_id.keys().last().copied().unwrap_or(0);
+    let mut issues = (1..=max_id)
+        .filter(|id| !lines_by_id.contains_key(id))
+        .map(|id| Day2IdIssue::Missing { id })
+        .collect::<Vec<_>>();
+    issues.extend(
+        lines_by_id
+            .into_iter()
+            .filter(|(_, lines)| lines.len() > 1)
+            .map(|(id, lines)| Day2IdIssue::Duplicate { id, lines }),
+    );
+    issues
+}
+
+pub fn day2_part1(inp: &str) -> usize {
+    day2_solve_part1(&day2_parse(inp).unwrap())
+}
+
+pub fn day2_solve_part1(games: &[Day2Game]) -> usize {
+    let bag = day2_part1_bag();
+    games
+        .iter()
+        .filter(|game| game.is_possible_with(&bag))
+        .map(|game| game.id)
+        .sum()
 }
 
 pub fn day2_part2(inp: &str) -> u32 {
-    return inp
-        .lines()
-        .map(|line| {
-            let i = line.find(": ").unwrap() + ": ".len();
-            let nums = line[i..].split("; ").fold((0, 0, 0), |acc, sub_game| {
-                return sub_game
-                    .split(", ")
-                    .fold(acc, |(red, green, blue), num_and_colour| {
-                        let num: u32 = num_and_colour.split(' ').next().unwrap().parse().unwrap();
-                        let colour = num_and_colour.split(' ').nth(1).unwrap();
-                        match colour {
-                            "red" => (red.max(num), green, blue),
-                            "green" => (red, green.max(num), blue),
-                            "blue" => (red, green, blue.max(num)),
-                            other => panic!("Unexpected colour: {}", other),
-                        }
-                    });
-            });
-            nums.0 * nums.1 * nums.2
+    day2_solve_part2(&day2_parse(inp).unwrap())
+}
+
+pub fn day2_solve_part2(games: &[Day2Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| {
+            let bag = game.minimal_bag();
+            ["red", "green", "blue"]
+                .iter()
+                .map(|&colour| bag.get(colour).copied().unwrap_or(0))
+                .product::<u32>()
         })
-        .sum();
+        .sum()
 }
 
-pub fn day3_part1(inp: &str) -> u32 {
-    let lines = inp.lines().collect::<Vec<_>>();
-    let num_of_rows = lines.len();
-    let num_of_cols = lines[0].len();
+/// A part number candidate: a run of digits in the schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day3Number {
+    pub row: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day3Schematic {
+    pub rows: Vec<String>,
+    pub numbers: Vec<Day3Number>,
+}
+
+impl Day3Schematic {
+    fn num_of_cols(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    fn symbol_at(&self, (r, c): (usize, usize)) -> char {
+        char::from(self.rows[r].as_bytes()[c])
+    }
+
+    /// The cells around a number, not including the number itself.
+    fn cells_around(&self, number: &Day3Number) -> Vec<(usize, usize)> {
+        let rows = number.row.saturating_sub(1)..=(number.row + 1).min(self.rows.len() - 1);
+        let cols =
+            number.start_col.saturating_sub(1)..=(number.end_col + 1).min(self.num_of_cols() - 1);
+        rows.flat_map(|r| cols.clone().map(move |c| (r, c)))
+            .filter(|&(r, c)| r != number.row || c < number.start_col || c > number.end_col)
+            .collect()
+    }
+}
+
+pub fn day3_parse(inp: &str) -> Result<Day3Schematic, String> {
+    let inp = &*normalise_input(inp);
+    let rows = inp.lines().map(|line| line.to_owned()).collect::<Vec<_>>();
+    let num_of_cols = rows.first().ok_or("Schematic is empty")?.len();
+    if num_of_cols == 0 {
+        return Err("Line 1 is empty".to_owned());
+    }
     let re = Regex::new("[0-9]+").unwrap();
-    re.find_iter(inp)
-        .filter(|re_match| {
-            let row_match_is_in = re_match.start() / (num_of_cols + 1);
-            let match_start_col = re_match.start() % (num_of_cols + 1);
-            let match_end_col = match_start_col + re_match.len() - 1;
-            let mut cells_to_check = Vec::new();
-
-            if row_match_is_in > 0 {
-                (match_start_col..(match_end_col + 1)).for_each(|c| {
-                    cells_to_check.push((row_match_is_in - 1, c));
-                });
-                if match_start_col > 0 {
-                    cells_to_check.push((row_match_is_in - 1, match_start_col - 1));
-                }
-                if match_end_col < (num_of_cols - 1) {
-                    cells_to_check.push((row_match_is_in - 1, match_end_col + 1));
-                }
-            }
-            if row_match_is_in < (num_of_rows - 1) {
-                (match_start_col..(match_end_col + 1)).for_each(|c| {
-                    cells_to_check.push((row_match_is_in + 1, c));
-                });
-                if match_start_col > 0 {
-                    cells_to_check.push((row_match_is_in + 1, match_start_col - 1));
-                }
-                if match_end_col < (num_of_cols - 1) {
-                    cells_to_check.push((row_match_is_in + 1, match_end_col + 1));
-                }
-            }
-            if match_start_col > 0 {
-                cells_to_check.push((row_match_is_in, match_start_col - 1));
-            }
-            if match_end_col < (num_of_cols - 1) {
-                cells_to_check.push((row_match_is_in, match_end_col + 1));
-            }
+    let mut numbers = Vec::new();
+    for (row, line) in rows.iter().enumerate() {
+        if !line.is_ascii() {
+            return Err(format!("Line {} has non-ASCII characters", row + 1));
+        }
+        if line.len() != num_of_cols {
+            return Err(format!(
+                "Line {} has {} columns, expected {}",
+                row + 1,
+                line.len(),
+                num_of_cols
+            ));
+        }
+        for re_match in re.find_iter(line) {
+            let value = re_match.as_str().parse::<u32>().map_err(|e| {
+                format!("Line {}: bad number {}: {}", row + 1, re_match.as_str(), e)
+            })?;
+            numbers.push(Day3Number {
+                row,
+                start_col: re_match.start(),
+                end_col: re_match.end() - 1,
+                value,
+            });
+        }
+    }
+    Ok(Day3Schematic { rows, numbers })
+}
+
+pub fn day3_part1(inp: &str) -> u32 {
+    day3_solve_part1(&day3_parse(inp).unwrap())
+}
 
-            cells_to_check.iter().any(|(r, c)| {
-                let symbol = lines[*r].chars().nth(*c).unwrap();
+pub fn day3_solve_part1(schematic: &Day3Schematic) -> u32 {
+    schematic
+        .numbers
+        .iter()
+        .filter(|number| {
+            schematic.cells_around(number).into_iter().any(|cell| {
+                let symbol = schematic.symbol_at(cell);
                 symbol != '.' && symbol.is_numeric().not()
             })
         })
-        .map(|re_match| re_match.as_str().parse::<u32>().unwrap())
+        .map(|number| number.value)
         .sum()
 }
 
 pub fn day3_part2(inp: &str) -> u32 {
-    let lines = inp.lines().collect::<Vec<_>>();
-    let num_of_rows = lines.len();
-    let num_of_cols = lines[0].len();
-    let re = Regex::new("[0-9]+").unwrap();
-    let mut gears = HashMap::new();
-    re.find_iter(inp).for_each(|re_match| {
-        let row_match_is_in = re_match.start() / (num_of_cols + 1);
-        let match_start_col = re_match.start() % (num_of_cols + 1);
-        let match_end_col = match_start_col + re_match.len() - 1;
-        let mut cells_to_check = Vec::new();
-
-        if row_match_is_in > 0 {
-            (match_start_col..(match_end_col + 1)).for_each(|c| {
-                cells_to_check.push((row_match_is_in - 1, c));
-            });
-            if match_start_col > 0 {
-                cells_to_check.push((row_match_is_in - 1, match_start_col - 1));
-            }
-            if match_end_col < (num_of_cols - 1) {
-                cells_to_check.push((row_match_is_in - 1, match_end_col + 1));
-            }
-        }
-        if row_match_is_in < (num_of_rows - 1) {
-            (match_start_col..(match_end_col + 1)).for_each(|c| {
-                cells_to_check.push((row_match_is_in + 1, c));
-            });
-            if match_start_col > 0 {
-                cells_to_check.push((row_match_is_in + 1, match_start_col - 1));
-            }
-            if match_end_col < (num_of_cols - 1) {
-                cells_to_check.push((row_match_is_in + 1, match_end_col + 1));
-            }
-        }
-        if match_start_col > 0 {
-            cells_to_check.push((row_match_is_in, match_start_col - 1));
-        }
-        if match_end_col < (num_of_cols - 1) {
-            cells_to_check.push((row_match_is_in, match_end_col + 1));
-        }
+    day3_solve_part2(&day3_parse(inp).unwrap())
+}
 
-        cells_to_check
-            .iter()
-            .filter(|(r, c)| {
-                let symbol = lines[*r].chars().nth(*c).unwrap();
-                symbol == '*'
-            })
-            .for_each(|&gear| {
-                let part_num = re_match.as_str().parse::<u32>().unwrap();
+pub fn day3_solve_part2(schematic: &Day3Schematic) -> u32 {
+    let mut gears = HashMap::new();
+    schematic.numbers.iter().for_each(|number| {
+        schematic
+            .cells_around(number)
+            .into_iter()
+            .filter(|&cell| schematic.symbol_at(cell) == '*')
+            .for_each(|gear| {
                 gears
                     .entry(gear)
                     .and_modify(|lst: &mut Vec<u32>| {
-                        lst.push(part_num);
+                        lst.push(number.value);
                     })
-                    .or_insert(vec![part_num]);
+                    .or_insert(vec![number.value]);
             });
     });
     gears
@@ -234,44 +576,87 @@ pub fn day3_part2(inp: &str) -> u32 {
         .sum()
 }
 
-pub fn day4_part1(inp: &str) -> usize {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day4Card {
+    pub id: usize,
+    pub winning_nums: HashSet<u32>,
+    pub nums_you_have: HashSet<u32>,
+}
+
+impl Day4Card {
+    pub fn num_of_matching_nums(&self) -> usize {
+        self.winning_nums.intersection(&self.nums_you_have).count()
+    }
+}
+
+/// The id and the two lists of numbers on a card, in the order they're written.
+fn day4_split_card(s: &str) -> Result<(usize, Vec<u32>, Vec<u32>), String> {
+    let (card_str, scratchcard) = s.split_once(':').ok_or("Missing `:`")?;
+    let id = card_str
+        .strip_prefix("Card")
+        .ok_or(format!("Expected `Card <id>`, got: {}", card_str))?
+        .trim()
+        .parse()
+        .map_err(|e| format!("Bad card id {}: {}", card_str, e))?;
+    let (winning_str, you_have_str) = scratchcard.split_once('|').ok_or("Missing `|`")?;
+    let parse_nums = |nums_str: &str| {
+        nums_str
+            .split_ascii_whitespace()
+            .map(|num_str| {
+                num_str
+                    .parse()
+                    .map_err(|e| format!("Bad number {}: {}", num_str, e))
+            })
+            .collect::<Result<_, _>>()
+    };
+    Ok((id, parse_nums(winning_str)?, parse_nums(you_have_str)?))
+}
+
+impl FromStr for Day4Card {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, winning_nums, nums_you_have) = day4_split_card(s)?;
+        Ok(Day4Card {
+            id,
+            winning_nums: winning_nums.into_iter().collect(),
+            nums_you_have: nums_you_have.into_iter().collect(),
+        })
+    }
+}
+
+pub fn day4_parse(inp: &str) -> Result<Vec<Day4Card>, String> {
+    let inp = &*normalise_input(inp);
     inp.lines()
-        .map(|line| {
-            let scratchcard = line.split_once(':').unwrap().1.split_once('|').unwrap();
-            let winning_nums = scratchcard
-                .0
-                .split_ascii_whitespace()
-                .collect::<HashSet<_>>();
-            let nums_you_have = scratchcard
-                .1
-                .split_ascii_whitespace()
-                .collect::<HashSet<_>>();
-            match winning_nums.intersection(&nums_you_have).count() {
-                0 => 0,
-                num_of_winning_nums => 1 << (num_of_winning_nums - 1),
-            }
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+pub fn day4_part1(inp: &str) -> usize {
+    day4_solve_part1(&day4_parse(inp).unwrap())
+}
+
+pub fn day4_solve_part1(cards: &[Day4Card]) -> usize {
+    cards
+        .iter()
+        .map(|card| match card.num_of_matching_nums() {
+            0 => 0,
+            num_of_winning_nums => 1 << (num_of_winning_nums - 1),
         })
         .sum()
 }
 
 pub fn day4_part2(inp: &str) -> usize {
-    // Parse cards into a map from card num to (freq, matching numbers)
-    let mut card_map = inp
-        .lines()
+    day4_solve_part2(&day4_parse(inp).unwrap())
+}
+
+pub fn day4_solve_part2(cards: &[Day4Card]) -> usize {
+    // Turn cards into a map from card num to (freq, matching numbers)
+    let mut card_map = cards
+        .iter()
         .enumerate()
-        .map(|(iteration_num, line)| {
-            let scratchcard = line.split_once(':').unwrap().1.split_once('|').unwrap();
-            let winning_nums = scratchcard
-                .0
-                .split_ascii_whitespace()
-                .collect::<HashSet<_>>();
-            let nums_you_have = scratchcard
-                .1
-                .split_ascii_whitespace()
-                .collect::<HashSet<_>>();
-            let num_of_matching_nums = winning_nums.intersection(&nums_you_have).count();
-            (iteration_num + 1, (1, num_of_matching_nums))
-        })
+        .map(|(iteration_num, card)| (iteration_num + 1, (1, card.num_of_matching_nums())))
         .collect::<HashMap<_, _>>();
     // Iterate through map in ascending order, modifying freq along the way
     for iteration_num in 1..(card_map.len() + 1) {
@@ -288,193 +673,351 @@ pub fn day4_part2(inp: &str) -> usize {
     card_map.values().map(|(freq, _)| freq).sum()
 }
 
-pub fn day5_part1(inp: &str) -> u64 {
-    // parse seeds as vec and maps as Vec<(u64, u64, u64)>
-    let seeds = inp
-        .lines()
-        .next()
-        .unwrap()
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split(' ')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u64>>();
-    let maps = inp
-        .split("\n\n")
-        .skip(1)
-        .map(|map_str| {
-            map_str
-                .lines()
-                .skip(1)
-                .map(|map_line| {
-                    let nums = map_line
-                        .split(' ')
-                        .map(|s| s.parse().unwrap())
-                        .collect::<Vec<u64>>();
-                    (nums[0], nums[1], nums[2])
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-    // for each seed:
-    seeds
-        .iter()
-        .map(|&seed| {
-            // run it through each map
-            maps.iter().fold(seed, |acc, map| {
-                map.iter()
-                    .filter_map(|&(dest_range_start, source_range_start, range_len)| {
-                        if (source_range_start..(source_range_start + range_len)).contains(&acc) {
-                            Some(dest_range_start + (acc - source_range_start))
-                        } else {
-                            None
-                        }
-                    })
-                    .next()
-                    .unwrap_or(acc)
-            })
-        })
-        // find the min final value
-        .min()
-        .unwrap()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day4ValidationMode {
+    /// Any issue makes validation fail.
+    Strict,
+    /// Issues are returned as warnings and the input is accepted as-is.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day4Side {
+    Winning,
+    YouHave,
+}
+
+/// Something in a day 4 input that `day4_part1`/`day4_part2` would silently paper over.
+/// All line numbers are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Day4Issue {
+    /// The line is not of the form `Card N: nums | nums`.
+    Malformed { line: usize },
+    /// A number appears more than once on the same side of the `|`.
+    DuplicateNumber {
+        line: usize,
+        side: Day4Side,
+        number: u32,
+    },
+    /// The `Card N:` id doesn't match the card's position in the input.
+    UnexpectedCardId {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The card wins copies of cards past the end of the table, which get dropped.
+    CopiesPastLastCard {
+        line: usize,
+        num_of_dropped_cards: usize,
+    },
+}
+
+fn day4_find_duplicates(line: usize, side: Day4Side, nums: &[u32], issues: &mut Vec<Day4Issue>) {
+    let mut seen = HashSet::new();
+    for &number in nums {
+        if !seen.insert(number) {
+            issues.push(Day4Issue::DuplicateNumber { line, side, number });
+        }
+    }
 }
 
-pub fn day5_part2(inp: &str) -> u64 {
-    // parse seeds as vec and maps as Vec<(u64, u64, u64)>
-    let first_line = inp
-        .lines()
+/// Check a day 4 input for duplicate numbers, card ids that don't match their position,
+/// and cards that win copies past the last card. Lines that `day4_parse` would reject are
+/// reported as `Malformed`.
+///
+/// In `Strict` mode any issue is an error. In `Lenient` mode the issues are returned
+/// as warnings. An input with no `Malformed` lines can still be fed to
+/// `day4_part1`/`day4_part2`, but one with them can't.
+pub fn day4_validate(
+    inp: &str,
+    mode: Day4ValidationMode,
+) -> Result<Vec<Day4Issue>, Vec<Day4Issue>> {
+    let inp = &*normalise_input(inp);
+    let mut issues = Vec::new();
+    let num_of_cards = inp.lines().count();
+    for (i, line_str) in inp.lines().enumerate() {
+        let line = i + 1;
+        let Ok((id, winning_nums, nums_you_have)) = day4_split_card(line_str) else {
+            issues.push(Day4Issue::Malformed { line });
+            continue;
+        };
+        day4_find_duplicates(line, Day4Side::Winning, &winning_nums, &mut issues);
+        day4_find_duplicates(line, Day4Side::YouHave, &nums_you_have, &mut issues);
+        if id != line {
+            issues.push(Day4Issue::UnexpectedCardId {
+                line,
+                expected: line,
+                found: id,
+            });
+        }
+        let num_of_matching_nums = Day4Card {
+            id,
+            winning_nums: winning_nums.into_iter().collect(),
+            nums_you_have: nums_you_have.into_iter().collect(),
+        }
+        .num_of_matching_nums();
+        if line + num_of_matching_nums > num_of_cards {
+            issues.push(Day4Issue::CopiesPastLastCard {
+                line,
+                num_of_dropped_cards: line + num_of_matching_nums - num_of_cards,
+            });
+        }
+    }
+    match mode {
+        Day4ValidationMode::Strict if !issues.is_empty() => Err(issues),
+        _ => Ok(issues),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day5Almanac {
+    pub seeds: Vec<u64>,
+    /// Each map is a list of (dest_range_start, source_range_start, range_len).
+    pub maps: Vec<Vec<(u64, u64, u64)>>,
+    /// What each map converts from, then what the last one converts to, e.g. `seed`,
+    /// `soil`, ..., `location`.
+    pub categories: Vec<String>,
+}
+
+fn day5_parse_num(s: &str) -> Result<u64, String> {
+    s.parse().map_err(|e| format!("Bad number {}: {}", s, e))
+}
+
+pub fn day5_parse(inp: &str) -> Result<Day5Almanac, String> {
+    let inp = &*normalise_input(inp);
+    let mut sections = inp.split("\n\n");
+    let seeds = sections
         .next()
         .unwrap()
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split(' ')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u64>>();
-    let maps = inp
-        .split("\n\n")
-        .skip(1)
+        .strip_prefix("seeds:")
+        .ok_or("Expected `seeds: ...` on the first line")?
+        .split_ascii_whitespace()
+        .map(day5_parse_num)
+        .collect::<Result<Vec<_>, _>>()?;
+    // Both parts need at least one seed. Part 2 checks that they pair up into ranges.
+    if seeds.is_empty() {
+        return Err("No seeds".to_owned());
+    }
+    let mut categories = Vec::new();
+    let maps = sections
         .map(|map_str| {
-            map_str
-                .lines()
-                .skip(1)
+            let mut lines = map_str.lines();
+            let header = lines.next().unwrap_or_default();
+            let (from, to) = header
+                .strip_suffix(" map:")
+                .and_then(|name| name.split_once("-to-"))
+                .ok_or(format!(
+                    "Expected a `FROM-to-TO map:` header, got: {}",
+                    header
+                ))?;
+            if categories.is_empty() {
+                categories.push(from.to_owned());
+            }
+            if categories.last().map(|last| last.as_str()) != Some(from) {
+                return Err(format!(
+                    "{}: expected a map from {}",
+                    header,
+                    categories.last().unwrap()
+                ));
+            }
+            categories.push(to.to_owned());
+            lines
                 .map(|map_line| {
                     let nums = map_line
                         .split(' ')
-                        .map(|s| s.parse().unwrap())
-                        .collect::<Vec<u64>>();
-                    (nums[0], nums[1], nums[2])
+                        .map(day5_parse_num)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| format!("{}: {}", header, e))?;
+                    let &[dest_range_start, source_range_start, range_len] = &nums[..] else {
+                        return Err(format!("{}: expected 3 numbers, got: {}", header, map_line));
+                    };
+                    if dest_range_start.checked_add(range_len).is_none()
+                        || source_range_start.checked_add(range_len).is_none()
+                    {
+                        return Err(format!("{}: range overflows: {}", header, map_line));
+                    }
+                    Ok((dest_range_start, source_range_start, range_len))
                 })
-                .collect::<Vec<_>>()
+                .collect()
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<_, _>>()?;
+    Ok(Day5Almanac {
+        seeds,
+        maps,
+        categories,
+    })
+}
 
-    let nums = first_line;
-    let x = nums
-        .iter()
-        .enumerate()
-        .filter(|(n, _)| n % 2 == 0)
-        .map(|(_, v)| v);
-    let y = nums
-        .iter()
-        .enumerate()
-        .filter(|(n, _)| n % 2 == 1)
-        .map(|(_, v)| v);
-    let seeds_iter = x
-        .zip(y)
-        .flat_map(|(&start, &length)| start..(start + length));
-    // for each seed:
-    seeds_iter
-        .enumerate()
-        .map(|(n, seed)| {
-            if n % 100_000_000 == 0 {
-                println!("On iteration {}", n);
+/// Convert a value with one map. Values outside all of its ranges are unchanged.
+pub fn day5_map(map: &[(u64, u64, u64)], value: u64) -> u64 {
+    map.iter()
+        .filter_map(|&(dest_range_start, source_range_start, range_len)| {
+            if (source_range_start..(source_range_start + range_len)).contains(&value) {
+                Some(dest_range_start + (value - source_range_start))
+            } else {
+                None
             }
-            // run it through each map
-            maps.iter().fold(seed, |acc, map| {
-                map.iter()
-                    .filter_map(|&(dest_range_start, source_range_start, range_len)| {
-                        if (source_range_start..(source_range_start + range_len)).contains(&acc) {
-                            Some(dest_range_start + (acc - source_range_start))
-                        } else {
-                            None
-                        }
-                    })
-                    .next()
-                    .unwrap_or(acc)
-            })
         })
+        .next()
+        .unwrap_or(value)
+}
+
+/// Run a value of `category` through the rest of the maps, giving it in each category
+/// from `category` to the last, or `None` if there's no such category.
+pub fn day5_trace<'a>(
+    almanac: &'a Day5Almanac,
+    category: &str,
+    value: u64,
+) -> Option<Vec<(&'a str, u64)>> {
+    let start = almanac.categories.iter().position(|c| c == category)?;
+    let mut trace = vec![(almanac.categories[start].as_str(), value)];
+    for (map, to) in almanac.maps[start..]
+        .iter()
+        .zip(&almanac.categories[start + 1..])
+    {
+        let value = day5_map(map, trace.last().unwrap().1);
+        trace.push((to, value));
+    }
+    Some(trace)
+}
+
+/// Run a seed through each map in turn.
+fn day5_location(maps: &[Vec<(u64, u64, u64)>], seed: u64) -> u64 {
+    maps.iter().fold(seed, |acc, map| day5_map(map, acc))
+}
+
+pub fn day5_part1(inp: &str) -> u64 {
+    day5_solve_part1(&day5_parse(inp).unwrap())
+}
+
+pub fn day5_solve_part1(almanac: &Day5Almanac) -> u64 {
+    maybe_par_iter!(&almanac.seeds)
+        .map(|&seed| day5_location(&almanac.maps, seed))
         // find the min final value
         .min()
         .unwrap()
 }
 
-pub fn day6_part1(inp: &str) -> usize {
-    let times = inp
-        .lines()
-        .next()
-        .unwrap()
-        .split_once(':')
-        .unwrap()
-        .1
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<f64>().unwrap());
-    let distances = inp
-        .lines()
-        .nth(1)
-        .unwrap()
-        .split_once(':')
-        .unwrap()
-        .1
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<f64>().unwrap());
-    times
-        .zip(distances)
-        .map(|(t, d)| {
-            let min_num = (t - f64::sqrt(t.powf(2.0) - 4.0 * d)) / 2_f64;
-            let max_num = (t + f64::sqrt(t.powf(2.0) - 4.0 * d)) / 2_f64;
-            let min_actual_num = if min_num.ceil() == min_num {
-                min_num + 1.0
-            } else {
-                min_num.ceil()
-            };
-            let max_actual_num = if max_num.floor() == max_num {
-                max_num - 1.0
-            } else {
-                max_num.floor()
-            };
-            let nums = (min_actual_num as u64)..((max_actual_num as u64) + 1);
-            println!("t {} d {} {:?}", t, d, nums);
-            nums.count()
+pub fn day5_part2(inp: &str) -> Result<u64, String> {
+    day5_solve_part2(&day5_parse(inp)?)
+}
+
+pub fn day5_solve_part2(almanac: &Day5Almanac) -> Result<u64, String> {
+    uncancelled(day5_solve_part2_with(
+        almanac,
+        &Silent,
+        &CancellationToken::new(),
+    ))
+}
+
+/// `day5_solve_part2`, reporting progress every 100 million seeds since it takes a while, and
+/// stopping early if `cancel` is cancelled. The seeds have to pair up into (start, length)
+/// ranges that don't overflow, and at least one range has to be non-empty.
+pub fn day5_solve_part2_with(
+    almanac: &Day5Almanac,
+    observer: &dyn Observer,
+    cancel: &CancellationToken,
+) -> Result<u64, SolveError> {
+    const CHUNK_LEN: u64 = 1_000_000;
+    let Day5Almanac { seeds, maps, .. } = almanac;
+    if seeds.len() % 2 != 0 {
+        return Err(SolveError::BadInput(format!(
+            "Expected seeds in (start, length) pairs, got {} numbers",
+            seeds.len()
+        )));
+    }
+    for pair in seeds.chunks_exact(2) {
+        pair[0]
+            .checked_add(pair[1])
+            .ok_or(format!("Seed range {} {} overflows", pair[0], pair[1]))?;
+    }
+    if seeds.chunks_exact(2).all(|pair| pair[1] == 0) {
+        return Err(SolveError::BadInput("Every seed range is empty".to_owned()));
+    }
+    // (start, length, how many seeds come before this chunk) for chunks of the seed
+    // ranges, so that the token is checked between chunks
+    let mut chunks = Vec::new();
+    let mut total = 0;
+    for pair in seeds.chunks_exact(2) {
+        let (range_start, range_len) = (pair[0], pair[1]);
+        for offset in (0..range_len).step_by(CHUNK_LEN as usize) {
+            let len = CHUNK_LEN.min(range_len - offset);
+            chunks.push((range_start + offset, len, total + offset));
+        }
+        total += range_len;
+    }
+    let chunk_mins = maybe_par_iter!(0..chunks.len())
+        .map(|i| {
+            let (start, len, before) = chunks[i];
+            cancel.check()?;
+            if i % 100 == 0 {
+                observer.progress(before, Some(total));
+            }
+            Ok((start..(start + len))
+                .map(|seed| day5_location(maps, seed))
+                .min())
         })
-        .product()
+        .collect::<Result<Vec<_>, Cancelled>>()?;
+    // find the min final value
+    Ok(chunk_mins.into_iter().flatten().min().unwrap())
 }
 
-pub fn day6_part2(inp: &str) -> usize {
-    let t = inp
-        .lines()
-        .next()
-        .unwrap()
-        .split_once(':')
-        .unwrap()
-        .1
-        .split_ascii_whitespace()
-        .collect::<String>()
-        .parse::<f64>()
-        .unwrap();
-    let d = inp
-        .lines()
-        .nth(1)
-        .unwrap()
-        .split_once(':')
-        .unwrap()
-        .1
-        .split_ascii_whitespace()
-        .collect::<String>()
-        .parse::<f64>()
-        .unwrap();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day6Races {
+    /// (time, record distance) for each race.
+    pub races: Vec<(u64, u64)>,
+    /// The race you get by ignoring the spaces between the numbers, for part 2.
+    pub long_race: (u64, u64),
+}
+
+pub fn day6_parse(inp: &str) -> Result<Day6Races, String> {
+    let inp = &*normalise_input(inp);
+    let mut lines = inp.lines();
+    let mut parse_line = |label: &str| {
+        let nums_str = lines
+            .next()
+            .and_then(|line| line.strip_prefix(label))
+            .ok_or(format!("Expected a `{} ...` line", label))?;
+        let nums = nums_str
+            .split_ascii_whitespace()
+            .map(|s| {
+                s.parse::<u64>()
+                    .map_err(|e| format!("Bad number {}: {}", s, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let long_num = nums_str
+            .split_ascii_whitespace()
+            .collect::<String>()
+            .parse::<u64>()
+            .map_err(|e| {
+                format!(
+                    "{} doesn't fit when the numbers are joined up: {}",
+                    label, e
+                )
+            })?;
+        Ok::<_, String>((nums, long_num))
+    };
+    let (times, long_time) = parse_line("Time:")?;
+    let (distances, long_distance) = parse_line("Distance:")?;
+    if times.len() != distances.len() {
+        return Err(format!(
+            "{} times but {} distances",
+            times.len(),
+            distances.len()
+        ));
+    }
+    Ok(Day6Races {
+        races: times.into_iter().zip(distances).collect(),
+        long_race: (long_time, long_distance),
+    })
+}
 
+fn day6_ways_to_win(t: f64, d: f64, observer: &dyn Observer) -> usize {
+    // Even holding for half the time doesn't beat the record
+    if t.powf(2.0) < 4.0 * d {
+        observer.debug(format_args!("t {} d {} can't be won", t, d));
+        return 0;
+    }
     let min_num = (t - f64::sqrt(t.powf(2.0) - 4.0 * d)) / 2_f64;
     let max_num = (t + f64::sqrt(t.powf(2.0) - 4.0 * d)) / 2_f64;
     let min_actual_num = if min_num.ceil() == min_num {
@@ -488,12 +1031,65 @@ pub fn day6_part2(inp: &str) -> usize {
         max_num.floor()
     };
     let nums = (min_actual_num as u64)..((max_actual_num as u64) + 1);
-    println!("t {} d {} {:?}", t, d, nums);
+    observer.debug(format_args!("t {} d {} {:?}", t, d, nums));
     nums.count()
 }
 
-#[derive(Eq, PartialEq, PartialOrd, Ord)]
-enum Day7Part1HandType {
+pub fn day6_part1(inp: &str) -> usize {
+    day6_solve_part1(&day6_parse(inp).unwrap())
+}
+
+pub fn day6_solve_part1(races: &Day6Races) -> usize {
+    day6_solve_part1_observed(races, &Silent)
+}
+
+/// `day6_solve_part1`, reporting the range of winning hold times for each race.
+pub fn day6_solve_part1_observed(races: &Day6Races, observer: &dyn Observer) -> usize {
+    races
+        .races
+        .iter()
+        .map(|&(t, d)| day6_ways_to_win(t as f64, d as f64, observer))
+        .product()
+}
+
+pub fn day6_part2(inp: &str) -> usize {
+    day6_solve_part2(&day6_parse(inp).unwrap())
+}
+
+pub fn day6_solve_part2(races: &Day6Races) -> usize {
+    day6_solve_part2_observed(races, &Silent)
+}
+
+/// `day6_solve_part2`, reporting the range of winning hold times.
+pub fn day6_solve_part2_observed(races: &Day6Races, observer: &dyn Observer) -> usize {
+    let (t, d) = races.long_race;
+    day6_ways_to_win(t as f64, d as f64, observer)
+}
+
+fn day6_ways_to_win_by_simulation(time: u64, distance: u64) -> usize {
+    (0..=time)
+        .filter(|hold_time| hold_time * (time - hold_time) > distance)
+        .count()
+}
+
+/// Reference for `day6_part1` that tries every hold time instead of solving the quadratic.
+pub fn day6_part1_reference(inp: &str) -> usize {
+    day6_parse(inp)
+        .unwrap()
+        .races
+        .into_iter()
+        .map(|(t, d)| day6_ways_to_win_by_simulation(t, d))
+        .product()
+}
+
+/// Reference for `day6_part2` that tries every hold time instead of solving the quadratic.
+pub fn day6_part2_reference(inp: &str) -> usize {
+    let (t, d) = day6_parse(inp).unwrap().long_race;
+    day6_ways_to_win_by_simulation(t, d)
+}
+
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Day7Part1HandType {
     HighCard,
     OnePair,
     TwoPair,
@@ -522,13 +1118,13 @@ impl FromStr for Day7Part1HandType {
             [2, 2, 1] => Day7Part1HandType::TwoPair,
             [2, 1, 1, 1] => Day7Part1HandType::OnePair,
             [1, 1, 1, 1, 1] => Day7Part1HandType::HighCard,
-            _ => panic!("Unexpected hand type"),
+            _ => return Err(format!("Unexpected hand: {}", s)),
         })
     }
 }
 
-#[derive(Eq, PartialEq, PartialOrd, Ord)]
-enum Day7Part2HandType {
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Day7Part2HandType {
     HighCard,
     OnePair,
     TwoPair,
@@ -564,7 +1160,7 @@ impl FromStr for Day7Part2HandType {
             [2, 2, 1] => Day7Part2HandType::TwoPair,
             [2, 1, 1, 1] => Day7Part2HandType::OnePair,
             [1, 1, 1, 1, 1] => Day7Part2HandType::HighCard,
-            _ => panic!("Unexpected hand type"),
+            _ => return Err(format!("Unexpected hand: {}", s)),
         })
     }
 }
@@ -581,7 +1177,7 @@ impl FromStr for Day7Part1HandAndType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Day7Part1HandAndType {
             hand: s.to_owned(),
-            hand_type: s.parse::<Day7Part1HandType>().unwrap(),
+            hand_type: s.parse::<Day7Part1HandType>()?,
         })
     }
 }
@@ -628,7 +1224,7 @@ impl FromStr for Day7Part2HandAndType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Day7Part2HandAndType {
             hand: s.to_owned(),
-            hand_type: s.parse::<Day7Part2HandType>().unwrap(),
+            hand_type: s.parse::<Day7Part2HandType>()?,
         })
     }
 }
@@ -663,154 +1259,296 @@ fn day7_part2_secondary_compare_hands(a: &str, b: &str) -> Ordering {
     panic!("Hands are equal. Maybe this is expected. But panicking for now.");
 }
 
-struct Day7Part1HandAndBid {
-    hand_and_type: Day7Part1HandAndType,
-    bid: usize,
-}
-
-impl FromStr for Day7Part1HandAndBid {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand_str, bid_str) = s.split_once(' ').ok_or("Parsing failed")?;
-        Ok(Day7Part1HandAndBid {
-            hand_and_type: hand_str.parse::<Day7Part1HandAndType>().unwrap(),
-            bid: bid_str.parse().unwrap(),
-        })
+/// Check that a hand is 5 cards from `23456789TJQKA`.
+fn day7_check_cards(cards: &str) -> Result<(), String> {
+    match cards.len() == 5 && cards.chars().all(|c| "23456789TJQKA".contains(c)) {
+        true => Ok(()),
+        false => Err(format!(
+            "Expected 5 cards from 23456789TJQKA, got: {}",
+            cards
+        )),
     }
 }
 
-struct Day7Part2HandAndBid {
-    hand_and_type: Day7Part2HandAndType,
-    bid: usize,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day7Hand {
+    pub cards: String,
+    pub bid: usize,
 }
 
-impl FromStr for Day7Part2HandAndBid {
+impl FromStr for Day7Hand {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (hand_str, bid_str) = s.split_once(' ').ok_or("Parsing failed")?;
-        Ok(Day7Part2HandAndBid {
-            hand_and_type: hand_str.parse::<Day7Part2HandAndType>().unwrap(),
-            bid: bid_str.parse().unwrap(),
+        day7_check_cards(hand_str)?;
+        Ok(Day7Hand {
+            cards: hand_str.to_owned(),
+            bid: bid_str
+                .parse()
+                .map_err(|e| format!("Bad bid {}: {}", bid_str, e))?,
         })
     }
 }
 
+/// Parse the hands, rejecting repeated hands since there's no way to rank them.
+pub fn day7_parse(inp: &str) -> Result<Vec<Day7Hand>, String> {
+    let inp = &*normalise_input(inp);
+    let mut seen = HashSet::new();
+    inp.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let hand = line
+                .parse::<Day7Hand>()
+                .map_err(|e| format!("Line {}: {}", i + 1, e))?;
+            if !seen.insert(hand.cards.clone()) {
+                return Err(format!("Line {}: hand {} appears twice", i + 1, hand.cards));
+            }
+            Ok(hand)
+        })
+        .collect()
+}
+
 pub fn day7_part1(inp: &str) -> usize {
-    // Parse input into list of (hand, bid)
-    let mut hand_and_bid_lst = inp
-        .lines()
-        .map(|line| line.parse::<Day7Part1HandAndBid>().unwrap())
+    day7_solve_part1(&day7_parse(inp).unwrap())
+}
+
+/// The hands from weakest to strongest by part 1's rules.
+pub fn day7_rank_part1(hands: &[Day7Hand]) -> Vec<&Day7Hand> {
+    let mut ranked = hands
+        .iter()
+        .map(|hand| (hand.cards.parse::<Day7Part1HandAndType>().unwrap(), hand))
         .collect::<Vec<_>>();
-    // Order list by hand
-    hand_and_bid_lst.sort_unstable_by(|a, b| a.hand_and_type.cmp(&b.hand_and_type));
+    ranked.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    ranked.into_iter().map(|(_, hand)| hand).collect()
+}
+
+pub fn day7_solve_part1(hands: &[Day7Hand]) -> usize {
     // Multiply each bid by rank and return sum
-    hand_and_bid_lst
+    day7_rank_part1(hands)
         .iter()
         .enumerate()
-        .map(|(i, hand_and_bid)| (i + 1) * hand_and_bid.bid)
+        .map(|(i, hand)| (i + 1) * hand.bid)
         .sum()
 }
 
 pub fn day7_part2(inp: &str) -> usize {
-    // Parse input into list of (hand, bid)
-    let mut hand_and_bid_lst = inp
-        .lines()
-        .map(|line| line.parse::<Day7Part2HandAndBid>().unwrap())
+    day7_solve_part2(&day7_parse(inp).unwrap())
+}
+
+/// The hands from weakest to strongest by part 2's rules, where `J` is a joker.
+pub fn day7_rank_part2(hands: &[Day7Hand]) -> Vec<&Day7Hand> {
+    let mut ranked = hands
+        .iter()
+        .map(|hand| (hand.cards.parse::<Day7Part2HandAndType>().unwrap(), hand))
         .collect::<Vec<_>>();
-    // Order list by hand
-    hand_and_bid_lst.sort_unstable_by(|a, b| a.hand_and_type.cmp(&b.hand_and_type));
+    ranked.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    ranked.into_iter().map(|(_, hand)| hand).collect()
+}
+
+pub fn day7_solve_part2(hands: &[Day7Hand]) -> usize {
     // Multiply each bid by rank and return sum
-    hand_and_bid_lst
+    day7_rank_part2(hands)
         .iter()
         .enumerate()
-        .map(|(i, hand_and_bid)| (i + 1) * hand_and_bid.bid)
+        .map(|(i, hand)| (i + 1) * hand.bid)
         .sum()
 }
 
-enum Day8Direction {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day8Direction {
     L,
     R,
 }
 
 impl Day8Direction {
-    fn from_char(c: &char) -> Day8Direction {
+    fn from_char(c: &char) -> Result<Day8Direction, String> {
         match c {
-            'L' => Day8Direction::L,
-            'R' => Day8Direction::R,
-            otherwise => panic!("Unexpected char: {}", otherwise),
+            'L' => Ok(Day8Direction::L),
+            'R' => Ok(Day8Direction::R),
+            otherwise => Err(format!("Unexpected char: {}", otherwise)),
         }
     }
 }
 
-pub fn day8_part1(inp: &str) -> usize {
-    // Parse into first line (as LR) and Map<str, (str, str)>
-    let (first_str, snd_str) = inp.split_once("\n\n").unwrap();
-    let instructions = first_str.chars().map(|c| Day8Direction::from_char(&c));
-    let network = snd_str
-        .lines()
-        .map(|line| {
-            let (f, s) = line.split_once(" = ").unwrap();
-            let (s_1, s_2) = s[1..s.len() - 1].split_once(", ").unwrap();
-            (f, (s_1, s_2))
-        })
-        .collect::<HashMap<&str, (&str, &str)>>();
-    // Cycle through LR, start at AAA, count steps to ZZZ
-    let mut curr_node = "AAA";
-    for (count, instruction) in instructions.cycle().enumerate() {
-        if curr_node == "ZZZ" {
-            return count;
-        }
-        let (left, right) = network.get(curr_node).unwrap();
-        curr_node = match instruction {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day8Network {
+    pub instructions: Vec<Day8Direction>,
+    /// Map from each node to its (left, right) neighbours.
+    pub nodes: HashMap<String, (String, String)>,
+}
+
+impl Day8Network {
+    /// The node reached from `node` by following one instruction.
+    pub fn step(&self, node: &str, instruction: &Day8Direction) -> &str {
+        let (left, right) = self.nodes.get(node).unwrap();
+        match instruction {
             Day8Direction::L => left,
             Day8Direction::R => right,
         }
     }
-    panic!("Shouldn't get here");
+
+    /// The nodes visited by following `steps` instructions from `start`, including it, or
+    /// `None` if there's no such node.
+    pub fn walk<'a>(&'a self, start: &'a str, steps: usize) -> Option<Vec<&'a str>> {
+        self.nodes.get(start)?;
+        let mut path = vec![start];
+        for instruction in self.instructions.iter().cycle().take(steps) {
+            path.push(self.step(path.last().unwrap(), instruction));
+        }
+        Some(path)
+    }
 }
 
-pub fn day8_part2(inp: &str) -> u64 {
-    // Parse into first line (as LR) and Map<str, (str, str)>
-    let (first_str, snd_str) = inp.split_once("\n\n").unwrap();
+/// Parse the network, checking that every node it refers to is defined. Which nodes the
+/// parts start and end on is left to each part to check.
+pub fn day8_parse(inp: &str) -> Result<Day8Network, String> {
+    let inp = &*normalise_input(inp);
+    let (first_str, snd_str) = inp
+        .split_once("\n\n")
+        .ok_or("Expected a blank line after the instructions")?;
     let instructions = first_str
         .chars()
         .map(|c| Day8Direction::from_char(&c))
-        .collect::<Vec<_>>();
-    let network = snd_str
-        .lines()
-        .map(|line| {
-            let (f, s) = line.split_once(" = ").unwrap();
-            let (s_1, s_2) = s[1..s.len() - 1].split_once(", ").unwrap();
-            (f, (s_1, s_2))
-        })
-        .collect::<HashMap<&str, (&str, &str)>>();
-    let mut curr_nodes = snd_str
-        .lines()
-        .map(|line| line.split_once(" = ").unwrap().0)
+        .collect::<Result<Vec<_>, _>>()?;
+    if instructions.is_empty() {
+        return Err("No instructions".to_owned());
+    }
+    let mut nodes = HashMap::new();
+    for line in snd_str.lines() {
+        let (f, s) = line
+            .split_once(" = ")
+            .and_then(|(f, s)| Some((f, s.strip_prefix('(')?.strip_suffix(')')?)))
+            .ok_or(format!("Expected `NODE = (LEFT, RIGHT)`, got: {}", line))?;
+        let (s_1, s_2) = s
+            .split_once(", ")
+            .ok_or(format!("Expected `NODE = (LEFT, RIGHT)`, got: {}", line))?;
+        if nodes
+            .insert(f.to_owned(), (s_1.to_owned(), s_2.to_owned()))
+            .is_some()
+        {
+            return Err(format!("Node {} is defined twice", f));
+        }
+    }
+    for (left, right) in nodes.values() {
+        if let Some(missing) = [left, right].into_iter().find(|n| !nodes.contains_key(*n)) {
+            return Err(format!("Node {} is never defined", missing));
+        }
+    }
+    Ok(Day8Network {
+        instructions,
+        nodes,
+    })
+}
+
+pub fn day8_part1(inp: &str) -> Result<usize, String> {
+    day8_solve_part1(&day8_parse(inp)?)
+}
+
+pub fn day8_solve_part1(network: &Day8Network) -> Result<usize, String> {
+    uncancelled(day8_solve_part1_with(network, &CancellationToken::new()))
+}
+
+/// Follow the instructions from `start` until reaching a node that `is_end`, checking
+/// `cancel` after each pass through the instructions since there might not be one.
+fn day8_steps_until<'a>(
+    network: &'a Day8Network,
+    start: &'a str,
+    is_end: impl Fn(&str) -> bool,
+    cancel: &CancellationToken,
+) -> Result<usize, Cancelled> {
+    let mut curr_node = start;
+    for (count, instruction) in network.instructions.iter().cycle().enumerate() {
+        if is_end(curr_node) {
+            return Ok(count);
+        }
+        if count % network.instructions.len() == 0 {
+            cancel.check()?;
+        }
+        curr_node = network.step(curr_node, instruction);
+    }
+    panic!("Shouldn't get here");
+}
+
+/// `day8_solve_part1`, stopping early if `cancel` is cancelled. The network has to have
+/// both an `AAA` and a `ZZZ` node.
+pub fn day8_solve_part1_with(
+    network: &Day8Network,
+    cancel: &CancellationToken,
+) -> Result<usize, SolveError> {
+    if let Some(missing) = ["AAA", "ZZZ"]
+        .into_iter()
+        .find(|n| !network.nodes.contains_key(*n))
+    {
+        return Err(SolveError::BadInput(format!("There's no {} node", missing)));
+    }
+    // Cycle through LR, start at AAA, count steps to ZZZ
+    Ok(day8_steps_until(
+        network,
+        "AAA",
+        |node| node == "ZZZ",
+        cancel,
+    )?)
+}
+
+pub fn day8_part2(inp: &str) -> Result<u64, String> {
+    day8_solve_part2(&day8_parse(inp)?)
+}
+
+pub fn day8_solve_part2(network: &Day8Network) -> Result<u64, String> {
+    uncancelled(day8_solve_part2_with(network, &CancellationToken::new()))
+}
+
+/// `day8_solve_part2`, stopping early if `cancel` is cancelled. The network has to have
+/// nodes ending with A to start on and ones ending with Z to end on.
+pub fn day8_solve_part2_with(
+    network: &Day8Network,
+    cancel: &CancellationToken,
+) -> Result<u64, SolveError> {
+    let start_nodes = network
+        .nodes
+        .keys()
+        .map(|node| node.as_str())
         .filter(|node| node.ends_with('A'))
         .collect::<Vec<_>>();
+    if start_nodes.is_empty() {
+        return Err(SolveError::BadInput("No nodes end with A".to_owned()));
+    }
+    if !network.nodes.keys().any(|node| node.ends_with('Z')) {
+        return Err(SolveError::BadInput("No nodes end with Z".to_owned()));
+    }
     // This code assumes that each node ending with A will eventually go to a unique node ending with Z.
     // And that after that, the path will loop the same number of iterations to get back to the same Z node.
     // I checked this held for the input we have to run the code on.
-    curr_nodes
-        .iter_mut()
-        .map(|curr_node| {
-            for (count, instruction) in instructions.iter().cycle().enumerate() {
-                if curr_node.ends_with('Z') {
-                    return count as u64;
-                }
-                let (left, right) = network.get(curr_node).unwrap();
-                *curr_node = match instruction {
-                    Day8Direction::L => left,
-                    Day8Direction::R => right,
-                }
-            }
-            panic!("Shouldn't get here");
+    let cycle_lengths = maybe_par_iter!(start_nodes)
+        .map(|start| {
+            day8_steps_until(network, start, |node| node.ends_with('Z'), cancel)
+                .map(|count| count as u64)
         })
-        .reduce(|a, b| a.lcm(&b))
-        .unwrap()
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cycle_lengths.into_iter().reduce(|a, b| a.lcm(&b)).unwrap())
+}
+
+/// Reference for `day8_part2` that steps all the ghosts together until they're all on a
+/// node ending with Z, rather than assuming each ghost loops and taking the LCM. Only
+/// usable when the answer is small.
+pub fn day8_part2_reference(inp: &str) -> u64 {
+    let network = day8_parse(inp).unwrap();
+    let mut curr_nodes = network
+        .nodes
+        .keys()
+        .map(|node| node.as_str())
+        .filter(|node| node.ends_with('A'))
+        .collect::<Vec<_>>();
+    for (count, instruction) in network.instructions.iter().cycle().enumerate() {
+        if curr_nodes.iter().all(|node| node.ends_with('Z')) {
+            return count as u64;
+        }
+        for curr_node in curr_nodes.iter_mut() {
+            *curr_node = network.step(curr_node, instruction);
+        }
+    }
+    panic!("Shouldn't get here");
 }
 
 fn day9_create_pyramid_of_differences(sequence: Vec<BigRational>) -> Vec<Vec<BigRational>> {
@@ -853,7 +1591,7 @@ pub fn day9_compute_coefficients(mut sequence: Vec<BigRational>) -> Vec<(BigRati
         let pyramid = day9_create_pyramid_of_differences(sequence.clone());
         let depth = pyramid.len() - 1;
         // Compute next coefficient
-        let next_coefficient = pyramid.last().unwrap().get(0).unwrap() / day9_factorial(depth);
+        let next_coefficient = pyramid.last().unwrap().first().unwrap() / day9_factorial(depth);
         // Modify the sequence
         for (n, elem) in sequence.iter_mut().enumerate() {
             *elem = elem.clone()
@@ -889,109 +1627,674 @@ pub fn day9_eval_coeffs_at_position(
     res.to_integer()
 }
 
-pub fn day9_part1(inp: &str) -> BigInt {
-    // Parse input
-    let sequences: Vec<Vec<BigRational>> = inp
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect()
+pub fn day9_parse(inp: &str) -> Result<Vec<Vec<i64>>, String> {
+    let inp = &*normalise_input(inp);
+    inp.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let sequence = line
+                .split_ascii_whitespace()
+                .map(|s| {
+                    s.parse()
+                        .map_err(|e| format!("Line {}: bad number {}: {}", i + 1, s, e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if sequence.is_empty() {
+                return Err(format!("Line {} is empty", i + 1));
+            }
+            Ok(sequence)
         })
-        .collect();
-    let seq_lens = sequences.iter().map(|seq| seq.len()).collect::<Vec<_>>();
-    sequences
-        .into_iter()
-        .map(day9_compute_coefficients)
-        .zip(seq_lens)
-        .map(|(coefficients, seq_len)| {
-            day9_eval_coeffs_at_position(&coefficients, seq_len.try_into().unwrap())
+        .collect()
+}
+
+fn day9_to_rational(sequence: &[i64]) -> Vec<BigRational> {
+    sequence
+        .iter()
+        .map(|&n| BigRational::from_integer(n.into()))
+        .collect()
+}
+
+pub fn day9_part1(inp: &str) -> BigInt {
+    day9_solve_part1(&day9_parse(inp).unwrap())
+}
+
+pub fn day9_solve_part1(sequences: &[Vec<i64>]) -> BigInt {
+    maybe_par_iter!(sequences)
+        .map(|sequence| {
+            let coefficients = day9_compute_coefficients(day9_to_rational(sequence));
+            day9_eval_coeffs_at_position(&coefficients, sequence.len().try_into().unwrap())
         })
         .sum()
 }
 
 pub fn day9_part2(inp: &str) -> BigInt {
-    // Parse input
-    let sequences: Vec<Vec<BigRational>> = inp
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect()
-        })
-        .collect();
-    sequences
-        .into_iter()
-        .map(day9_compute_coefficients)
+    day9_solve_part2(&day9_parse(inp).unwrap())
+}
+
+pub fn day9_solve_part2(sequences: &[Vec<i64>]) -> BigInt {
+    maybe_par_iter!(sequences)
+        .map(|sequence| day9_compute_coefficients(day9_to_rational(sequence)))
         .map(|coefficients| day9_eval_coeffs_at_position(&coefficients, -1))
         .sum()
 }
 
+/// The next and previous values of a sequence, found by repeatedly taking differences
+/// until they're all zero. A sequence that runs out of differences first is taken to be
+/// constant from its last difference on, as when fitting a polynomial, so one number on
+/// its own just repeats.
+fn day9_extrapolate_by_differencing(sequence: Vec<i64>) -> (i64, i64) {
+    let mut rows = vec![sequence];
+    loop {
+        let last_row = rows.last().unwrap();
+        if last_row.len() <= 1 || last_row.iter().all(|&n| n == 0) {
+            break;
+        }
+        let next_row = last_row.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+        rows.push(next_row);
+    }
+    let next = rows.iter().map(|row| row.last().unwrap()).sum();
+    let prev = rows
+        .iter()
+        .rev()
+        .fold(0, |below, row| row.first().unwrap() - below);
+    (next, prev)
+}
+
+/// Reference for `day9_part1` using repeated differencing instead of fitting a polynomial.
+pub fn day9_part1_reference(inp: &str) -> BigInt {
+    day9_parse(inp)
+        .unwrap()
+        .into_iter()
+        .map(|sequence| BigInt::from(day9_extrapolate_by_differencing(sequence).0))
+        .sum()
+}
+
+/// Reference for `day9_part2` using repeated differencing instead of fitting a polynomial.
+pub fn day9_part2_reference(inp: &str) -> BigInt {
+    day9_parse(inp)
+        .unwrap()
+        .into_iter()
+        .map(|sequence| BigInt::from(day9_extrapolate_by_differencing(sequence).1))
+        .sum()
+}
+
+/// A day's parsed input, of whichever type its parser returns, so that all days can
+/// share one table.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+pub type ParseFn = fn(&str) -> Result<Parsed, String>;
+
+pub type SolveFn = fn(&dyn Any, &dyn Observer, &CancellationToken) -> Result<String, SolveError>;
+
+/// Why a solver gave no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input parsed, but this part can't be solved from it, for this reason.
+    BadInput(String),
+    /// The cancellation token was cancelled.
+    Cancelled,
+}
+
+impl From<Cancelled> for SolveError {
+    fn from(_: Cancelled) -> Self {
+        SolveError::Cancelled
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        SolveError::BadInput(message)
+    }
+}
+
+/// A registered day: its parser and a solver for each part, which take what the parser
+/// returned. Answers are rendered as strings so that solutions with different answer
+/// types can share one table. Solvers that have nothing to report ignore the observer,
+/// and ones that are always quick ignore the cancellation token. Input that only one
+/// part can't handle is rejected by that part's solver rather than the parser.
+pub struct Solution {
+    pub day: u8,
+    pub parse: ParseFn,
+    pub parts: [SolveFn; 2],
+}
+
+impl Solution {
+    pub const fn new(day: u8, parse: ParseFn, parts: [SolveFn; 2]) -> Self {
+        Solution { day, parse, parts }
+    }
+
+    /// Parse the input and solve one part of it, without reporting or cancelling.
+    pub fn solve(&self, part: u8, inp: &str) -> Result<String, String> {
+        let solve = *usize::from(part)
+            .checked_sub(1)
+            .and_then(|i| self.parts.get(i))
+            .ok_or(format!("No part {}", part))?;
+        let parsed = (self.parse)(inp)?;
+        uncancelled(solve(&*parsed, &Silent, &CancellationToken::new()))
+    }
+}
+
+/// The result of a solver that was given a token nothing cancels.
+fn uncancelled<T>(result: Result<T, SolveError>) -> Result<T, String> {
+    match result {
+        Ok(answer) => Ok(answer),
+        Err(SolveError::BadInput(e)) => Err(e),
+        Err(SolveError::Cancelled) => unreachable!("Nothing cancels the token"),
+    }
+}
+
+fn boxed<T: Any + Send + Sync>(parsed: Result<T, String>) -> Result<Parsed, String> {
+    parsed.map(|parsed| Box::new(parsed) as Parsed)
+}
+
+fn downcast<T: Any>(parsed: &dyn Any) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("Solver given another day's input")
+}
+
+pub const SOLUTIONS: [Solution; 9] = [
+    Solution::new(
+        1,
+        |inp| boxed(day1_parse(inp)),
+        [
+            |parsed, _, _| Ok(day1_part1(downcast::<String>(parsed))?.to_string()),
+            |parsed, _, _| Ok(day1_part2(downcast::<String>(parsed))?.to_string()),
+        ],
+    ),
+    Solution::new(
+        2,
+        |inp| boxed(day2_parse(inp)),
+        [
+            |parsed, _, _| Ok(day2_solve_part1(downcast::<Vec<Day2Game>>(parsed)).to_string()),
+            |parsed, _, _| Ok(day2_solve_part2(downcast::<Vec<Day2Game>>(parsed)).to_string()),
+        ],
+    ),
+    Solution::new(
+        3,
+        |inp| boxed(day3_parse(inp)),
+        [
+            |parsed, _, _| Ok(day3_solve_part1(downcast::<Day3Schematic>(parsed)).to_string()),
+            |parsed, _, _| Ok(day3_solve_part2(downcast::<Day3Schematic>(parsed)).to_string()),
+        ],
+    ),
+    Solution::new(
+        4,
+        |inp| boxed(day4_parse(inp)),
+        [
+            |parsed, _, _| Ok(day4_solve_part1(downcast::<Vec<Day4Card>>(parsed)).to_string()),
+            |parsed, _, _| Ok(day4_solve_part2(downcast::<Vec<Day4Card>>(parsed)).to_string()),
+        ],
+    ),
+    Solution::new(
+        5,
+        |inp| boxed(day5_parse(inp)),
+        [
+            |parsed, _, _| Ok(day5_solve_part1(downcast::<Day5Almanac>(parsed)).to_string()),
+            |parsed, observer, cancel| {
+                day5_solve_part2_with(downcast::<Day5Almanac>(parsed), observer, cancel)
+                    .map(|answer| answer.to_string())
+            },
+        ],
+    ),
+    Solution::new(
+        6,
+        |inp| boxed(day6_parse(inp)),
+        [
+            |parsed, observer, _| {
+                Ok(day6_solve_part1_observed(downcast::<Day6Races>(parsed), observer).to_string())
+            },
+            |parsed, observer, _| {
+                Ok(day6_solve_part2_observed(downcast::<Day6Races>(parsed), observer).to_string())
+            },
+        ],
+    ),
+    Solution::new(
+        7,
+        |inp| boxed(day7_parse(inp)),
+        [
+            |parsed, _, _| Ok(day7_solve_part1(downcast::<Vec<Day7Hand>>(parsed)).to_string()),
+            |parsed, _, _| Ok(day7_solve_part2(downcast::<Vec<Day7Hand>>(parsed)).to_string()),
+        ],
+    ),
+    Solution::new(
+        8,
+        |inp| boxed(day8_parse(inp)),
+        [
+            |parsed, _, cancel| {
+                day8_solve_part1_with(downcast::<Day8Network>(parsed), cancel)
+                    .map(|answer| answer.to_string())
+            },
+            |parsed, _, cancel| {
+                day8_solve_part2_with(downcast::<Day8Network>(parsed), cancel)
+                    .map(|answer| answer.to_string())
+            },
+        ],
+    ),
+    Solution::new(
+        9,
+        |inp| boxed(day9_parse(inp)),
+        [
+            |parsed, _, _| Ok(day9_solve_part1(downcast::<Vec<Vec<i64>>>(parsed)).to_string()),
+            |parsed, _, _| Ok(day9_solve_part2(downcast::<Vec<Vec<i64>>>(parsed)).to_string()),
+        ],
+    ),
+];
+
+pub fn find_solution(day: u8) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
     use super::*;
+    use inputs::InputStore;
+
+    /// Each profile with an input for `day` and answers for both parts, with each part's
+    /// input and answer.
+    fn answered_profiles(day: u8) -> Vec<(String, [String; 2], [String; 2])> {
+        let mut answered = Vec::new();
+        for profile in inputs::profiles("inputs").unwrap() {
+            let store = InputStore::new("inputs", &profile);
+            let answers = store.answers().unwrap();
+            if let (true, Some(part1), Some(part2)) = (
+                store.has(day),
+                answers.get(&(day, 1)),
+                answers.get(&(day, 2)),
+            ) {
+                let answers = [part1.clone(), part2.clone()];
+                let inputs = [1, 2].map(|part| store.get_part(day, part).unwrap());
+                answered.push((profile, inputs, answers));
+            }
+        }
+        answered
+    }
+
+    /// Run `check` on each profile's inputs for `day` with its expected answers, asserting
+    /// that there's at least one profile with both.
+    fn for_each_profile(day: u8, check: impl Fn(&str, [&str; 2], [&str; 2])) {
+        let answered = answered_profiles(day);
+        assert!(
+            !answered.is_empty(),
+            "No profile has an input and answers for day {}",
+            day
+        );
+        for (profile, [inp1, inp2], [part1, part2]) in &answered {
+            check(profile, [inp1, inp2], [part1, part2]);
+        }
+    }
+
+    /// Check both parts of `day` against the answers of every profile.
+    fn check_profiles(day: u8) {
+        let solution = find_solution(day).unwrap();
+        for_each_profile(day, |profile, inputs, expected| {
+            for ((part, inp), expected) in (1..=2).zip(inputs).zip(expected) {
+                let answer = solution.solve(part, inp).unwrap();
+                assert_eq!(answer, expected, "{} day {} part {}", profile, day, part);
+            }
+        });
+    }
+
+    /// `check_profiles` for a day that's still being written, which passes until a profile
+    /// has an input and answers for it.
+    #[allow(dead_code)]
+    fn check_profiles_once_answered(day: u8) {
+        if !answered_profiles(day).is_empty() {
+            check_profiles(day);
+        }
+    }
+
+    #[test]
+    fn test_normalise_input() {
+        assert!(matches!(normalise_input("1 2\n3 4\n"), Cow::Borrowed(_)));
+        assert_eq!(normalise_input("\u{feff}1 2 \r\n3 4"), "1 2\n3 4\n");
+        assert_eq!(normalise_input("1 2\n3 4\n\n \n"), "1 2\n3 4\n");
+        assert_eq!(normalise_input(""), "");
+
+        let windows = read_input("inputs/default/day8.txt")
+            .unwrap()
+            .replace('\n', "\r\n");
+        assert_eq!(day8_part1(&format!("\u{feff}{}", windows)), Ok(20221));
+        let inp = "seeds: 79 14 55 13\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n52 50 48";
+        assert_eq!(day5_part1(inp), 13);
+        assert_eq!(day3_part1("467..114..  \r\n...*......\r\n\r\n"), 467);
+    }
 
     #[test]
     fn test_day1() {
-        let inp = fs::read_to_string("inputs/day1.txt").unwrap();
-        assert_eq!(day1_part1(&inp), 54159);
-        assert_eq!(day1_part2(&inp), 53866);
+        check_profiles(1);
+    }
+
+    #[test]
+    fn test_day1_decoder() {
+        let english = Day1Decoder::english();
+        assert_eq!(english.decode_line("xtwone3four"), Some(24));
+        assert_eq!(english.decode_line("eightwo"), Some(82));
+        assert_eq!(english.decode_line("7"), Some(77));
+        assert_eq!(english.decode_line("abc"), None);
+
+        let german =
+            Day1Decoder::new([("eins", 1), ("zwei", 2), ("drei", 3), ("sieben", 7)]).unwrap();
+        assert_eq!(
+            german.decode("zweinsieben\ndreins\n", Day1NoDigitsPolicy::Error),
+            Ok(27 + 31)
+        );
+
+        let roman =
+            Day1Decoder::new([("i", 1), ("ii", 2), ("iii", 3), ("iv", 4), ("v", 5)]).unwrap();
+        assert_eq!(roman.decode_line("iv"), Some(44));
+        assert_eq!(roman.decode_line("iii-v"), Some(35));
+
+        assert!(Day1Decoder::new([("", 0)]).is_err());
+    }
+
+    #[test]
+    fn test_day1_scan() {
+        let token = |start, end, value| Day1Token { start, end, value };
+        assert_eq!(day1_first_digit("xtwone3four", false), Some(token(6, 7, 3)));
+        assert_eq!(day1_first_digit("xtwone3four", true), Some(token(1, 4, 2)));
+        assert_eq!(day1_last_digit("xtwone3four", true), Some(token(7, 11, 4)));
+        assert_eq!(day1_last_digit("eightwo", true), Some(token(4, 7, 2)));
+        assert_eq!(day1_last_digit("abc", true), None);
+        let inp = read_input("inputs/default/day1.txt").unwrap();
+        assert_eq!(
+            day1_part2(&inp),
+            Day1Decoder::english().decode(&inp, Day1NoDigitsPolicy::Error)
+        );
+    }
+
+    #[test]
+    fn test_day1_no_digits() {
+        let inp = "a1b\n\nxyz\ntwo3\n";
+        assert_eq!(day1_sum(inp, false, Day1NoDigitsPolicy::Skip), Ok(11 + 33));
+        assert_eq!(
+            day1_sum(inp, true, Day1NoDigitsPolicy::CountAsZero),
+            Ok(11 + 23)
+        );
+        assert_eq!(
+            day1_sum(inp, false, Day1NoDigitsPolicy::Error),
+            Err("Line 2 has no digits".to_owned())
+        );
+        assert_eq!(
+            day1_part1("1\n\n2\n"),
+            Err("Line 2 has no digits".to_owned())
+        );
+        let solution = find_solution(1).unwrap();
+        assert_eq!(solution.solve(2, "1\ntwo\n"), Ok("33".to_owned()));
+        assert_eq!(
+            solution.solve(1, "1\ntwo\n"),
+            Err("Line 2 has no digits".to_owned())
+        );
+        let report = day1_report(inp, true, Day1NoDigitsPolicy::CountAsZero).unwrap();
+        assert_eq!(report.len(), 4);
+        assert_eq!(report[2].tokens, None);
+        assert_eq!(report[2].value, 0);
+        let two = Day1Token {
+            start: 0,
+            end: 3,
+            value: 2,
+        };
+        let three = Day1Token {
+            start: 3,
+            end: 4,
+            value: 3,
+        };
+        assert_eq!(report[3].tokens, Some((two, three)));
+        assert_eq!(
+            Day1Decoder::english().report(inp, Day1NoDigitsPolicy::Skip),
+            day1_report(inp, true, Day1NoDigitsPolicy::Skip)
+        );
     }
 
     #[test]
     fn test_day2() {
-        let inp = fs::read_to_string("inputs/day2.txt").unwrap();
-        assert_eq!(day2_part1(&inp), 2348);
-        assert_eq!(day2_part2(&inp), 76008);
+        check_profiles(2);
+        for_each_profile(2, |profile, [inp, _], _| {
+            assert_eq!(
+                day2_check_ids(&day2_parse(inp).unwrap()),
+                vec![],
+                "{}",
+                profile
+            );
+        });
+    }
+
+    #[test]
+    fn test_day2_ids() {
+        let inp = "Game 4: 1 red\nGame 2: 20 red\nGame 4: 2 blue\n";
+        assert_eq!(day2_part1(inp), 8);
+        assert_eq!(
+            day2_check_ids(&day2_parse(inp).unwrap()),
+            vec![
+                Day2IdIssue::Missing { id: 1 },
+                Day2IdIssue::Missing { id: 3 },
+                Day2IdIssue::Duplicate {
+                    id: 4,
+                    lines: vec![1, 3]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_day2_game() {
+        let game = "Game 7: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 purple"
+            .parse::<Day2Game>()
+            .unwrap();
+        assert_eq!(game.id, 7);
+        assert_eq!(game.draws.len(), 3);
+        assert!(!game.is_possible_with(&day2_part1_bag()));
+        let mut bag = game.minimal_bag();
+        assert_eq!(bag.len(), 4);
+        assert_eq!(bag["blue"], 6);
+        assert_eq!(bag["purple"], 2);
+        assert!(game.is_possible_with(&bag));
+        bag.insert("purple".to_owned(), 1);
+        assert!(!game.is_possible_with(&bag));
+        assert!("Game x: 1 red".parse::<Day2Game>().is_err());
+        assert!("Game 1: red".parse::<Day2Game>().is_err());
     }
 
     #[test]
     fn test_day3() {
-        let inp = fs::read_to_string("inputs/day3.txt").unwrap();
-        assert_eq!(day3_part1(&inp), 531932);
-        assert_eq!(day3_part2(&inp), 73646890);
+        check_profiles(3);
     }
 
     #[test]
     fn test_day4() {
-        let inp = fs::read_to_string("inputs/day4.txt").unwrap();
-        assert_eq!(day4_part1(&inp), 23750);
-        assert_eq!(day4_part2(&inp), 13261850);
+        check_profiles(4);
+        for_each_profile(4, |profile, [inp, _], _| {
+            let issues = day4_validate(inp, Day4ValidationMode::Strict);
+            assert_eq!(issues, Ok(vec![]), "{}", profile);
+        });
+    }
+
+    #[test]
+    fn test_day4_validate() {
+        let inp = "Card 1: 1 2 3 | 1 2 2\nCard 3: 4 5 | 4 5\nCard 3: 6 | 7\n";
+        let issues = vec![
+            Day4Issue::DuplicateNumber {
+                line: 1,
+                side: Day4Side::YouHave,
+                number: 2,
+            },
+            Day4Issue::UnexpectedCardId {
+                line: 2,
+                expected: 2,
+                found: 3,
+            },
+            Day4Issue::CopiesPastLastCard {
+                line: 2,
+                num_of_dropped_cards: 1,
+            },
+        ];
+        assert_eq!(
+            day4_validate(inp, Day4ValidationMode::Lenient),
+            Ok(issues.clone())
+        );
+        assert_eq!(day4_validate(inp, Day4ValidationMode::Strict), Err(issues));
+        assert_eq!(
+            day4_validate("Card 1: 1 2\n", Day4ValidationMode::Lenient),
+            Ok(vec![Day4Issue::Malformed { line: 1 }])
+        );
+        assert!(day4_parse("Card 1: 1 2\n").is_err());
     }
 
     #[test]
     fn test_day5() {
-        let inp = fs::read_to_string("inputs/day5.txt").unwrap();
-        assert_eq!(day5_part1(&inp), 389056265);
-        assert_eq!(day5_part2(&inp), 137516820);
+        check_profiles(5);
     }
 
     #[test]
     fn test_day6() {
-        let inp = fs::read_to_string("inputs/day6.txt").unwrap();
-        assert_eq!(day6_part1(&inp), 1660968);
-        assert_eq!(day6_part2(&inp), 26499773);
+        check_profiles(6);
+        for_each_profile(6, |profile, [inp1, inp2], [part1, part2]| {
+            assert_eq!(day6_part1_reference(inp1).to_string(), part1, "{}", profile);
+            assert_eq!(day6_part2_reference(inp2).to_string(), part2, "{}", profile);
+        });
+        // Races that can't be won, or only tie the record at best
+        let inp = "Time: 3 7 4\nDistance: 5 9 4\n";
+        assert_eq!(day6_part1(inp), 0);
+        assert_eq!(day6_part1_reference(inp), 0);
+        assert_eq!(day6_part1("Time: 7\nDistance: 10\n"), 2);
     }
+
     #[test]
     fn test_day7() {
-        let inp = fs::read_to_string("inputs/day7.txt").unwrap();
-        assert_eq!(day7_part1(&inp), 251545216);
-        assert_eq!(day7_part2(&inp), 250384185);
+        check_profiles(7);
     }
 
     #[test]
     fn test_day8() {
-        let inp = fs::read_to_string("inputs/day8.txt").unwrap();
-        assert_eq!(day8_part1(&inp), 20221);
-        assert_eq!(day8_part2(&inp), 14616363770447);
+        check_profiles(8);
     }
 
     #[test]
     fn test_day9() {
-        let inp = fs::read_to_string("inputs/day9.txt").unwrap();
-        assert_eq!(day9_part1(&inp), BigInt::from(1904165718));
-        assert_eq!(day9_part2(&inp), BigInt::from(964));
+        check_profiles(9);
+        for_each_profile(9, |profile, [inp1, inp2], [part1, part2]| {
+            assert_eq!(day9_part1_reference(inp1).to_string(), part1, "{}", profile);
+            assert_eq!(day9_part2_reference(inp2).to_string(), part2, "{}", profile);
+        });
+        // Too short to ever reach all zeros
+        let inp = "5\n1 2 4\n";
+        assert_eq!(day9_part1(inp), BigInt::from(5 + 7));
+        assert_eq!(day9_part1_reference(inp), day9_part1(inp));
+        assert_eq!(day9_part2_reference(inp), day9_part2(inp));
+    }
+
+    #[test]
+    fn test_parsers_reject_bad_input() {
+        assert!(day2_parse("Game 1: 3 blue\nGame 2 3 red\n")
+            .unwrap_err()
+            .starts_with("Line 2"));
+        assert!(day3_parse("").is_err());
+        assert!(day3_parse("..1\n.\n").is_err());
+        assert!(day3_parse("99999999999").is_err());
+        assert!(day4_parse("Card 1: 1 2 3\n").is_err());
+        assert!(day5_parse("seeds: 1 2\n\nseed-to-soil map:\n1 2\n").is_err());
+        assert!(day5_parse("seeds:\n\nseed-to-soil map:\n1 2 3\n").is_err());
+        assert!(day6_parse("Time: 7 15\nDistance: 9\n").is_err());
+        assert!(day6_parse("Time: 99999999999 99999999999\nDistance: 1 1\n").is_err());
+        assert!(day7_parse("32T3K 765\n32T3K 1\n").is_err());
+        assert!(day7_parse("32T3 765\n").is_err());
+        assert!(day8_parse("LR\n\nAAA = (BBB, ZZZ)\n").is_err());
+        assert!(day8_parse("\n\nAAA = (AAA, AAA)\n").is_err());
+        assert!(day8_parse("LX\n\nAAA = (AAA, AAA)\n").is_err());
+        assert!(day8_parse("L\n\nAAA = AAA, AAA\n").is_err());
+        assert!(day9_parse("1 2 3\n\n4 5 6\n").is_err());
+    }
+
+    #[test]
+    fn test_solvers_reject_bad_input() {
+        // Seeds that only part 2 can't read as ranges
+        assert_eq!(day5_part1("seeds: 79 14 55\n"), 14);
+        assert_eq!(
+            day5_part2("seeds: 79 14 55\n"),
+            Err("Expected seeds in (start, length) pairs, got 3 numbers".to_owned())
+        );
+        assert_eq!(day5_part1("seeds: 79 0 55 0\n"), 0);
+        assert_eq!(
+            day5_part2("seeds: 79 0 55 0\n"),
+            Err("Every seed range is empty".to_owned())
+        );
+        assert!(day5_part2("seeds: 18446744073709551615 1\n").is_err());
+        // Networks that only one part can walk
+        let inp = "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n";
+        assert_eq!(day8_part1(inp), Err("There's no AAA node".to_owned()));
+        assert_eq!(day8_part2(inp), Ok(1));
+        let inp = "L\n\nAAA = (AAA, AAA)\n";
+        assert_eq!(day8_part1(inp), Err("There's no ZZZ node".to_owned()));
+        assert_eq!(day8_part2(inp), Err("No nodes end with Z".to_owned()));
+        let inp = "L\n\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(day8_part2(inp), Err("No nodes end with A".to_owned()));
+    }
+
+    #[test]
+    fn test_observer() {
+        let observer = observer::WriteObserver::new(Vec::new());
+        let inp = "Time:      7  15   30\nDistance:  9  40  200\n";
+        assert_eq!(
+            day6_solve_part1_observed(&day6_parse(inp).unwrap(), &observer),
+            288
+        );
+        assert_eq!(
+            String::from_utf8(observer.into_inner()).unwrap(),
+            "t 7 d 9 2..6\nt 15 d 40 4..12\nt 30 d 200 11..20\n"
+        );
+
+        let observer = observer::WriteObserver::new(Vec::new());
+        let inp = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        assert_eq!(
+            day5_solve_part2_with(
+                &day5_parse(inp).unwrap(),
+                &observer,
+                &CancellationToken::new()
+            ),
+            Ok(57)
+        );
+        assert_eq!(
+            String::from_utf8(observer.into_inner()).unwrap(),
+            "progress: 0/27\n"
+        );
+    }
+
+    #[test]
+    fn test_cancel() {
+        let cancel = CancellationToken::new();
+        cancel.clone().cancel();
+        let almanac = day5_parse(&read_input("inputs/default/day5.txt").unwrap()).unwrap();
+        assert_eq!(
+            day5_solve_part2_with(&almanac, &Silent, &cancel),
+            Err(SolveError::Cancelled)
+        );
+        // ZZZ can't be reached, so this would never finish
+        let inp = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        let network = day8_parse(inp).unwrap();
+        assert_eq!(
+            day8_solve_part1_with(&network, &cancel),
+            Err(SolveError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_solutions() {
+        for day in 1..=9 {
+            assert_eq!(find_solution(day).unwrap().day, day);
+        }
+        assert!(find_solution(26).is_none());
+        let day7 = find_solution(7).unwrap();
+        assert_eq!(
+            day7.solve(2, "32T3K 765\nKTJJT 220\n"),
+            Ok("1205".to_owned())
+        );
+        assert!(day7.solve(1, "32T3K\n").is_err());
+
+        // Both parts can be solved from one parse
+        let parsed = (day7.parse)("32T3K 765\nKTJJT 220\nT55J5 684\n").unwrap();
+        let [part1, part2] = day7.parts;
+        let cancel = CancellationToken::new();
+        assert_eq!(part1(&*parsed, &Silent, &cancel), Ok("3257".to_owned()));
+        assert_eq!(part2(&*parsed, &Silent, &cancel), Ok("2793".to_owned()));
     }
 }
@@ -0,0 +1,521 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+    thread,
+    time::Duration,
+};
+
+use aoc2023::{
+    find_solution,
+    generators::{self, Day3Options, Day7Options, Day8Options},
+    inputs::{self, HttpFetcher, InputStore, DEFAULT_PROFILE},
+    observer::{Observer, Silent, WriteObserver},
+    read_input,
+    repl::Session,
+    report::{self, parse_answers, Answers, Record},
+    runner::{self, Job},
+    scaffold,
+    submit::{AttemptLog, Submitter},
+    watch::{describe_change, FileWatcher},
+    Solution, SOLUTIONS,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Days to run; all of them if none are given, skipping any without an input that
+    /// can't be downloaded, like the placeholders of new days
+    days: Vec<u8>,
+    /// Only run this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Print progress and debug output from the solutions to stderr
+    #[arg(long, short)]
+    verbose: bool,
+    /// Give up on a solution after this many seconds
+    #[arg(long)]
+    timeout: Option<f64>,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Expected answers, one `day part answer` per line. Defaults to `answers.txt` next to
+    /// each profile's inputs, and is ignored if it doesn't exist
+    #[arg(long)]
+    answers: Option<String>,
+    /// Use the inputs and answers under `inputs/<PROFILE>/`
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+    /// Run every profile under `inputs/`, skipping days a profile has no input for.
+    /// Missing inputs are never downloaded
+    #[arg(long, conflicts_with = "profile")]
+    all_profiles: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve days on their inputs in `inputs/<profile>/`, all at once. Missing inputs are
+    /// downloaded if `AOC_SESSION` is set to a session cookie
+    Run(RunArgs),
+    /// Re-run a day whenever its input, its example or their answers change, showing how
+    /// the answers differ from the previous run
+    Watch {
+        day: u8,
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Watch `inputs/<PROFILE>/` as well as `inputs/example/`
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+        /// Seconds between checks for changes
+        #[arg(long, default_value_t = 0.5)]
+        interval: f64,
+        /// Give up on a solution after this many seconds
+        #[arg(long)]
+        timeout: Option<f64>,
+    },
+    /// Explore a day's parsed input interactively. `help` lists the commands
+    Repl {
+        /// Load this day's input to begin with
+        day: Option<u8>,
+        /// Load inputs from `inputs/<PROFILE>/`
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+    },
+    /// Serve the solutions over HTTP on localhost: `GET /days` lists them, and
+    /// `POST /solve/{day}/{part}` solves the input in the body
+    #[cfg(feature = "server")]
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Give up on a solution after this many seconds
+        #[arg(long)]
+        timeout: Option<f64>,
+    },
+    /// Submit an answer, working it out first if it isn't given. Needs `AOC_SESSION` set
+    /// to a session cookie
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Add a day: a module for its solution, registered and tested against every profile,
+    /// with empty example and input files and commented-out answers to fill in
+    NewDay { day: u8 },
+    /// Print a synthetic input for a day
+    Generate {
+        day: u8,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Lines, games, cards, hands or races; grid side for day 3, seed ranges for
+        /// day 5, instructions for day 8
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+        /// Day 3: fraction of symbols that are `*`
+        #[arg(long)]
+        gear_density: Option<f64>,
+        /// Day 7: fraction of hands that are one pair
+        #[arg(long)]
+        tie_fraction: Option<f64>,
+        /// Day 8: each ghost's cycle length, in passes through the instructions
+        #[arg(long, value_delimiter = ',')]
+        cycles: Option<Vec<usize>>,
+        /// Write the input here instead of to stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+}
+
+fn generate(
+    day: u8,
+    seed: u64,
+    size: usize,
+    gear_density: Option<f64>,
+    tie_fraction: Option<f64>,
+    cycles: Option<Vec<usize>>,
+) -> Result<String, String> {
+    match (day, gear_density, tie_fraction, cycles) {
+        (3, Some(gear_density), None, None) => generators::day3(
+            seed,
+            &Day3Options {
+                rows: size,
+                cols: size,
+                gear_density,
+                ..Default::default()
+            },
+        ),
+        (7, None, Some(tie_fraction), None) => generators::day7(
+            seed,
+            &Day7Options {
+                num_hands: size,
+                tie_fraction,
+                ..Default::default()
+            },
+        ),
+        (8, None, None, Some(cycle_lengths)) => generators::day8(
+            seed,
+            &Day8Options {
+                num_instructions: size,
+                cycle_lengths,
+            },
+        ),
+        (_, None, None, None) => generators::generate(day, seed, size),
+        _ => Err(
+            "--gear-density, --tie-fraction and --cycles are only for days 3, 7 and 8 respectively"
+                .to_owned(),
+        ),
+    }
+}
+
+/// The records for one profile's run of `solutions`, with answers from `answers_path` or
+/// the profile's own. Missing or empty inputs are skipped if `skip_missing`, and
+/// downloaded if not. Days whose parts have different inputs are run once per part.
+fn run_profile(
+    profile: &str,
+    solutions: &[&'static Solution],
+    part: Option<u8>,
+    answers_path: Option<&str>,
+    skip_missing: bool,
+    observer: &dyn Observer,
+    timeout: Option<Duration>,
+) -> Result<Vec<Record>, String> {
+    let mut store = InputStore::new("inputs", profile);
+    if let (false, Ok(session)) = (skip_missing, env::var("AOC_SESSION")) {
+        store = store.with_fetcher(HttpFetcher::new(&session));
+    }
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let mut inputs = BTreeMap::new();
+    for solution in solutions {
+        if skip_missing && !store.has(solution.day) {
+            continue;
+        }
+        for &part in &parts {
+            inputs.insert((solution.day, part), store.get_part(solution.day, part)?);
+        }
+    }
+    let answers = match answers_path {
+        Some(path) => match read_input(path) {
+            Ok(answers) => parse_answers(&answers).map_err(|e| format!("{}: {}", path, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::new(),
+            Err(e) => return Err(format!("{}: {}", path, e)),
+        },
+        None => store.answers()?,
+    };
+    let mut jobs = Vec::new();
+    for &solution in solutions {
+        let Some(first) = inputs.get(&(solution.day, parts[0])) else {
+            continue;
+        };
+        if parts
+            .iter()
+            .all(|&part| inputs[&(solution.day, part)] == *first)
+        {
+            jobs.push(Job {
+                solution,
+                part,
+                input: first,
+            });
+        } else {
+            jobs.extend(parts.iter().map(|&part| Job {
+                solution,
+                part: Some(part),
+                input: &inputs[&(solution.day, part)],
+            }));
+        }
+    }
+    Ok(runner::run(&jobs, observer, timeout)
+        .iter()
+        .map(|outcome| {
+            Record::new(
+                profile,
+                outcome,
+                answers
+                    .get(&(outcome.day, outcome.part))
+                    .map(|answer| answer.as_str()),
+                &inputs[&(outcome.day, outcome.part)],
+            )
+        })
+        .collect())
+}
+
+/// A record as a line of text, e.g. `[example] Day 1 part 1: 142 (parse 1.00µs, solve
+/// 2.00µs)`.
+fn text_line(record: &Record, show_profile: bool) -> String {
+    let answer = record.answer.as_ref().or(record.error.as_ref()).unwrap();
+    let parse = Duration::from_nanos(record.parse_duration_ns as u64);
+    let solve = Duration::from_nanos(record.solve_duration_ns as u64);
+    let timings = format!("parse {:.2?}, solve {:.2?}", parse, solve);
+    let profile = match show_profile {
+        true => format!("[{}] ", record.profile),
+        false => String::new(),
+    };
+    match (record.matches(), &record.expected) {
+        (Some(false), Some(expected)) => format!(
+            "{}Day {} part {}: {} ({}), expected {}",
+            profile, record.day, record.part, answer, timings, expected
+        ),
+        _ => format!(
+            "{}Day {} part {}: {} ({})",
+            profile, record.day, record.part, answer, timings
+        ),
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let solutions = SOLUTIONS
+        .iter()
+        .filter(|solution| args.days.is_empty() || args.days.contains(&solution.day))
+        .collect::<Vec<_>>();
+    if solutions.is_empty() {
+        return Err("No solutions match".to_owned());
+    }
+    let profiles = match args.all_profiles {
+        true => inputs::profiles("inputs")?,
+        false => vec![args.profile.clone()],
+    };
+    let stderr = WriteObserver::new(io::stderr());
+    let observer: &dyn Observer = if args.verbose { &stderr } else { &Silent };
+    let timeout = args
+        .timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|e| format!("Bad timeout: {}", e))?;
+    // Other profiles' inputs can't be downloaded with this session, and without one only
+    // days asked for by name are worth failing over
+    let skip_missing =
+        args.all_profiles || (args.days.is_empty() && env::var("AOC_SESSION").is_err());
+    let mut records = Vec::new();
+    for profile in &profiles {
+        records.extend(run_profile(
+            profile,
+            &solutions,
+            args.part,
+            args.answers.as_deref(),
+            skip_missing,
+            observer,
+            timeout,
+        )?);
+    }
+    match args.format {
+        Format::Text => {
+            for record in &records {
+                println!("{}", text_line(record, args.all_profiles));
+            }
+        }
+        Format::Json => println!("{}", report::to_json(&records)),
+        Format::Csv => print!("{}", report::to_csv(&records)),
+    }
+    let failed = records
+        .iter()
+        .filter(|record| record.answer.is_none() || record.matches() == Some(false))
+        .count();
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} solution(s) failed", n)),
+    }
+}
+
+fn watch(
+    day: u8,
+    part: Option<u8>,
+    profile: &str,
+    interval: f64,
+    timeout: Option<f64>,
+) -> Result<(), String> {
+    let solution = find_solution(day).ok_or(format!("No solution for day {}", day))?;
+    let interval =
+        Duration::try_from_secs_f64(interval).map_err(|e| format!("Bad interval: {}", e))?;
+    let timeout = timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|e| format!("Bad timeout: {}", e))?;
+    let mut profiles = vec![profile.to_owned(), "example".to_owned()];
+    profiles.dedup();
+    let stores = profiles
+        .iter()
+        .map(|profile| InputStore::new("inputs", profile))
+        .collect::<Vec<_>>();
+    let mut watcher = FileWatcher::new(stores.iter().flat_map(|store| {
+        [
+            store.path(day),
+            store.part_path(day, 1),
+            store.part_path(day, 2),
+            store.dir().join("answers.txt"),
+        ]
+    }));
+    let mut previous = Vec::new();
+    let mut changed = vec![];
+    loop {
+        match changed.as_slice() {
+            [] => println!("Running day {}", day),
+            changed => println!(
+                "Running day {} after changes to {}",
+                day,
+                changed
+                    .iter()
+                    .map(|path: &PathBuf| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+        let mut records = Vec::new();
+        for profile in &profiles {
+            match run_profile(profile, &[solution], part, None, true, &Silent, timeout) {
+                Ok(profile_records) => records.extend(profile_records),
+                Err(e) => println!("[{}] {}", profile, e),
+            }
+        }
+        if records.is_empty() {
+            let paths = watcher.paths().map(|path| path.display().to_string());
+            println!(
+                "No inputs yet, waiting for {}",
+                paths.collect::<Vec<_>>().join(", ")
+            );
+        }
+        for record in &records {
+            match describe_change(&previous, record) {
+                change if change.is_empty() => println!("{}", text_line(record, true)),
+                change => println!("{} [{}]", text_line(record, true), change),
+            }
+        }
+        previous = records;
+        changed = loop {
+            thread::sleep(interval);
+            let changed = watcher.poll();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+    }
+}
+
+fn repl(day: Option<u8>, profile: &str) -> Result<(), String> {
+    let mut session = Session::new(InputStore::new("inputs", profile));
+    if let Some(day) = day {
+        println!("{}", session.load(day)?);
+    }
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        line.clear();
+        if io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            return Ok(());
+        }
+        match line.trim() {
+            "quit" | "exit" => return Ok(()),
+            command => match session.eval(command) {
+                Ok(out) if out.is_empty() => {}
+                Ok(out) => println!("{}", out),
+                Err(e) => println!("{}", e),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn serve(port: u16, timeout: Option<f64>) -> Result<(), String> {
+    let timeout = timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|e| format!("Bad timeout: {}", e))?;
+    let server = aoc2023::server::Server::bind(&format!("127.0.0.1:{}", port), timeout)?;
+    eprintln!("Listening on http://{}", server.local_addr().unwrap());
+    server.serve();
+    Ok(())
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) -> Result<(), String> {
+    let session = env::var("AOC_SESSION").map_err(|_| "AOC_SESSION isn't set".to_owned())?;
+    let store = InputStore::new("inputs", DEFAULT_PROFILE).with_fetcher(HttpFetcher::new(&session));
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution = find_solution(day).ok_or(format!("No solution for day {}", day))?;
+            solution.solve(part, &store.get_part(day, part)?)?
+        }
+    };
+    let submitter = Submitter::new(
+        &session,
+        AttemptLog::new(store.dir().join("attempts.jsonl")),
+    );
+    let response = submitter.submit(day, part, &answer)?;
+    match response.wait {
+        Some(wait) => println!("{}: {} (wait {:?})", answer, response.verdict, wait),
+        None => println!("{}: {}", answer, response.verdict),
+    }
+    Ok(())
+}
+
+fn new_day(day: u8) -> Result<(), String> {
+    for path in scaffold::new_day(".", day)? {
+        println!("Wrote {}", path.display());
+    }
+    println!(
+        "Fill in inputs/example/day{0}.txt and its answers, then solve it in src/day{0}.rs",
+        day
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Watch {
+            day,
+            part,
+            profile,
+            interval,
+            timeout,
+        } => watch(day, part, &profile, interval, timeout),
+        Command::Repl { day, profile } => repl(day, &profile),
+        #[cfg(feature = "server")]
+        Command::Serve { port, timeout } => serve(port, timeout),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+        Command::NewDay { day } => new_day(day),
+        Command::Generate {
+            day,
+            seed,
+            size,
+            gear_density,
+            tie_fraction,
+            cycles,
+            output,
+        } => generate(day, seed, size, gear_density, tie_fraction, cycles).and_then(|inp| {
+            match output {
+                Some(path) => fs::write(&path, inp).map_err(|e| format!("{}: {}", path, e)),
+                None => {
+                    print!("{}", inp);
+                    Ok(())
+                }
+            }
+        }),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
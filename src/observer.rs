@@ -0,0 +1,50 @@
+//! Hooks for solutions to report progress and debug information, so the library never
+//! writes to stdout itself. Callers pick what happens to the reports by choosing the
+//! observer they pass in.
+
+use std::{fmt, io::Write, sync::Mutex};
+
+pub trait Observer: Sync {
+    /// Called periodically by long-running solutions. `total` is given when known.
+    fn progress(&self, _done: u64, _total: Option<u64>) {}
+
+    /// Intermediate results that help when debugging a solution.
+    fn debug(&self, _message: fmt::Arguments) {}
+}
+
+/// Ignores everything.
+pub struct Silent;
+
+impl Observer for Silent {}
+
+/// Writes every report as a line to the given writer, e.g. `io::stderr()` or a file.
+pub struct WriteObserver<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriteObserver<W> {
+    pub fn new(writer: W) -> Self {
+        WriteObserver {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap()
+    }
+}
+
+impl<W: Write + Send> Observer for WriteObserver<W> {
+    fn progress(&self, done: u64, total: Option<u64>) {
+        let mut writer = self.writer.lock().unwrap();
+        // Reports are best-effort, so a failed write isn't worth stopping a solution for
+        let _ = match total {
+            Some(total) => writeln!(writer, "progress: {}/{}", done, total),
+            None => writeln!(writer, "progress: {}", done),
+        };
+    }
+
+    fn debug(&self, message: fmt::Arguments) {
+        let _ = writeln!(self.writer.lock().unwrap(), "{}", message);
+    }
+}
@@ -0,0 +1,245 @@
+//! The `aoc2023` Python extension module: the registered solutions, plus the parsed models
+//! of days 5, 7, 8 and 9 as classes backed by the same functions the solutions use.
+//!
+//! Errors from parsing or solving are raised as `ValueError`.
+
+use num::{BigInt, BigRational};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    day5_location, day5_parse, day5_trace, day7_check_cards, day7_parse, day7_rank_part1,
+    day7_rank_part2, day8_parse, day9_compute_coefficients, day9_eval_coeffs_at_position,
+    day9_to_rational, find_solution, Day5Almanac, Day7Hand, Day7Part1HandType, Day7Part2HandType,
+    Day8Direction, Day8Network, SOLUTIONS,
+};
+
+fn value_error(e: String) -> PyErr {
+    PyValueError::new_err(e)
+}
+
+/// The answer to a part of a day for an input.
+#[pyfunction]
+fn solve(day: u8, part: u8, input: &str) -> PyResult<String> {
+    let solution = find_solution(day).ok_or(value_error(format!("No solution for day {}", day)))?;
+    solution.solve(part, input).map_err(value_error)
+}
+
+/// The days with registered solutions, widened since pyo3 turns a `Vec<u8>` into `bytes`.
+#[pyfunction]
+fn days() -> Vec<u32> {
+    SOLUTIONS
+        .iter()
+        .map(|solution| u32::from(solution.day))
+        .collect()
+}
+
+/// A day 5 almanac.
+#[pyclass(name = "Almanac", frozen)]
+struct PyAlmanac(Day5Almanac);
+
+#[pymethods]
+impl PyAlmanac {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        day5_parse(input).map(PyAlmanac).map_err(value_error)
+    }
+
+    #[getter]
+    fn seeds(&self) -> Vec<u64> {
+        self.0.seeds.clone()
+    }
+
+    /// `seed`, `soil`, ..., `location`.
+    #[getter]
+    fn categories(&self) -> Vec<String> {
+        self.0.categories.clone()
+    }
+
+    /// Each map as a list of `(destination start, source start, length)`.
+    #[getter]
+    fn maps(&self) -> Vec<Vec<(u64, u64, u64)>> {
+        self.0.maps.clone()
+    }
+
+    /// A value of `category` in each category from there on, as `(category, value)`.
+    fn trace(&self, category: &str, value: u64) -> PyResult<Vec<(String, u64)>> {
+        let trace = day5_trace(&self.0, category, value)
+            .ok_or(value_error(format!("No such category: {}", category)))?;
+        Ok(trace
+            .into_iter()
+            .map(|(category, value)| (category.to_owned(), value))
+            .collect())
+    }
+
+    fn location(&self, seed: u64) -> u64 {
+        day5_location(&self.0.maps, seed)
+    }
+}
+
+/// A day 7 hand of five cards and its bid.
+#[pyclass(name = "Hand", frozen, from_py_object)]
+#[derive(Clone)]
+struct PyHand(Day7Hand);
+
+#[pymethods]
+impl PyHand {
+    #[new]
+    #[pyo3(signature = (cards, bid = 0))]
+    fn new(cards: &str, bid: usize) -> PyResult<Self> {
+        day7_check_cards(cards).map_err(value_error)?;
+        Ok(PyHand(Day7Hand {
+            cards: cards.to_owned(),
+            bid,
+        }))
+    }
+
+    /// Every hand in a day 7 input.
+    #[staticmethod]
+    fn parse(input: &str) -> PyResult<Vec<PyHand>> {
+        let hands = day7_parse(input).map_err(value_error)?;
+        Ok(hands.into_iter().map(PyHand).collect())
+    }
+
+    #[getter]
+    fn cards(&self) -> String {
+        self.0.cards.clone()
+    }
+
+    #[getter]
+    fn bid(&self) -> usize {
+        self.0.bid
+    }
+
+    /// The hand's type by `part`'s rules, e.g. `TwoPair`.
+    fn hand_type(&self, part: u8) -> PyResult<String> {
+        match part {
+            1 => Ok(format!(
+                "{:?}",
+                self.0
+                    .cards
+                    .parse::<Day7Part1HandType>()
+                    .map_err(value_error)?
+            )),
+            2 => Ok(format!(
+                "{:?}",
+                self.0
+                    .cards
+                    .parse::<Day7Part2HandType>()
+                    .map_err(value_error)?
+            )),
+            _ => Err(value_error(format!("No part {}", part))),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Hand('{}', {})", self.0.cards, self.0.bid)
+    }
+}
+
+/// Hands from weakest to strongest by `part`'s rules. Hands must all be different.
+#[pyfunction]
+fn rank(hands: Vec<PyHand>, part: u8) -> PyResult<Vec<PyHand>> {
+    let hands = hands.into_iter().map(|hand| hand.0).collect::<Vec<_>>();
+    let mut cards = hands.iter().map(|hand| &hand.cards).collect::<Vec<_>>();
+    cards.sort();
+    if let Some(pair) = cards.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(value_error(format!("Hand {} appears twice", pair[0])));
+    }
+    let ranked = match part {
+        1 => day7_rank_part1(&hands),
+        2 => day7_rank_part2(&hands),
+        _ => return Err(value_error(format!("No part {}", part))),
+    };
+    Ok(ranked.into_iter().cloned().map(PyHand).collect())
+}
+
+/// A day 8 network.
+#[pyclass(name = "Network", frozen)]
+struct PyNetwork(Day8Network);
+
+#[pymethods]
+impl PyNetwork {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        day8_parse(input).map(PyNetwork).map_err(value_error)
+    }
+
+    /// The instructions as a string of `L` and `R`.
+    #[getter]
+    fn instructions(&self) -> String {
+        self.0
+            .instructions
+            .iter()
+            .map(|instruction| match instruction {
+                Day8Direction::L => 'L',
+                Day8Direction::R => 'R',
+            })
+            .collect()
+    }
+
+    /// Every node's `(left, right)` neighbours.
+    #[getter]
+    fn nodes(&self) -> Vec<(String, (String, String))> {
+        let mut nodes = self.0.nodes.clone().into_iter().collect::<Vec<_>>();
+        nodes.sort();
+        nodes
+    }
+
+    /// The nodes visited by following `steps` instructions from `start`, including it.
+    fn walk(&self, start: &str, steps: usize) -> PyResult<Vec<String>> {
+        let path = self
+            .0
+            .walk(start, steps)
+            .ok_or(value_error(format!("No such node: {}", start)))?;
+        Ok(path.into_iter().map(|node| node.to_owned()).collect())
+    }
+}
+
+/// The polynomial through a day 9 sequence, with the sequence at positions 0, 1, ...
+#[pyclass(name = "Polynomial", frozen)]
+struct PyPolynomial(Vec<(BigRational, usize)>);
+
+#[pymethods]
+impl PyPolynomial {
+    #[staticmethod]
+    fn fit(sequence: Vec<i64>) -> PyResult<Self> {
+        if sequence.is_empty() {
+            return Err(value_error("Empty sequence".to_owned()));
+        }
+        Ok(PyPolynomial(day9_compute_coefficients(day9_to_rational(
+            &sequence,
+        ))))
+    }
+
+    /// `(numerator, denominator, power)` for each term, highest power first.
+    #[getter]
+    fn terms(&self) -> Vec<(BigInt, BigInt, usize)> {
+        self.0
+            .iter()
+            .map(|(coefficient, power)| {
+                (
+                    coefficient.numer().clone(),
+                    coefficient.denom().clone(),
+                    *power,
+                )
+            })
+            .collect()
+    }
+
+    /// The value at a position, which is a whole number at every whole position.
+    fn __call__(&self, position: i64) -> BigInt {
+        day9_eval_coeffs_at_position(&self.0, position)
+    }
+}
+
+#[pymodule(name = "aoc2023")]
+fn aoc2023_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(rank, m)?)?;
+    m.add_class::<PyAlmanac>()?;
+    m.add_class::<PyHand>()?;
+    m.add_class::<PyNetwork>()?;
+    m.add_class::<PyPolynomial>()?;
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Commands for poking at a day's parsed input interactively, built on the same functions
+//! the solutions use.
+
+use std::any::Any;
+
+use num::BigRational;
+
+use crate::{
+    day5_trace, day7_check_cards, day7_rank_part1, day7_rank_part2, day9_compute_coefficients,
+    day9_eval_coeffs_at_position, day9_to_rational, find_solution, inputs::InputStore, Day5Almanac,
+    Day7Hand, Day7Part1HandType, Day7Part2HandType, Day8Direction, Day8Network, Parsed,
+};
+
+pub const HELP: &str = "\
+load DAY              parse a day's input
+map CATEGORY VALUE    day 5: run a value through the almanac, e.g. `map seed 79`
+rank CARDS            day 7: a hand's type, and its rank among the input's hands if it's there
+walk NODE STEPS       day 8: follow the instructions from a node, e.g. `walk AAA 10`
+fit NUMBER...         day 9: fit a polynomial to a sequence and extrapolate both ways
+help                  show this
+quit                  leave";
+
+/// The input that's loaded, if any, and where to load inputs from.
+pub struct Session {
+    store: InputStore,
+    loaded: Option<(u8, Parsed)>,
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: &str) -> Result<T, String> {
+    arg.parse().map_err(|_| format!("Bad argument: {}", arg))
+}
+
+impl Session {
+    pub fn new(store: InputStore) -> Self {
+        Session {
+            store,
+            loaded: None,
+        }
+    }
+
+    /// Parse a day's input, replacing whatever was loaded.
+    pub fn load(&mut self, day: u8) -> Result<String, String> {
+        let solution = find_solution(day).ok_or(format!("No solution for day {}", day))?;
+        let parsed = (solution.parse)(&self.store.get(day)?)?;
+        self.loaded = Some((day, parsed));
+        Ok(format!("Loaded {}", self.store.path(day).display()))
+    }
+
+    /// The loaded input for `day`, if that's the day loaded.
+    fn model<T: Any>(&self, day: u8) -> Option<&T> {
+        match &self.loaded {
+            Some((loaded, parsed)) if *loaded == day => parsed.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    fn require<T: Any>(&self, day: u8) -> Result<&T, String> {
+        self.model(day)
+            .ok_or(format!("Needs day {0} loaded, e.g. `load {0}`", day))
+    }
+
+    /// Run one command, returning what to print.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let words = line.split_ascii_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_owned()),
+            ["load", day] => self.load(parse_arg(day)?),
+            ["map", category, value] => self.map(category, parse_arg(value)?),
+            ["rank", cards] => self.rank(cards),
+            ["walk", node, steps] => self.walk(node, parse_arg(steps)?),
+            ["fit", ref numbers @ ..] if !numbers.is_empty() => fit(&numbers
+                .iter()
+                .map(|n| parse_arg(n))
+                .collect::<Result<Vec<_>, _>>()?),
+            _ => Err(format!("Unknown command: {}. Try `help`", line.trim())),
+        }
+    }
+
+    fn map(&self, category: &str, value: u64) -> Result<String, String> {
+        let almanac = self.require::<Day5Almanac>(5)?;
+        let trace = day5_trace(almanac, category, value).ok_or(format!(
+            "No such category: {}. There's {}",
+            category,
+            almanac.categories.join(", ")
+        ))?;
+        let steps = trace
+            .iter()
+            .map(|(category, value)| format!("{} {}", category, value))
+            .collect::<Vec<_>>();
+        Ok(steps.join(" -> "))
+    }
+
+    fn rank(&self, cards: &str) -> Result<String, String> {
+        day7_check_cards(cards)?;
+        let mut out = format!(
+            "part 1: {:?}, part 2: {:?}",
+            cards.parse::<Day7Part1HandType>()?,
+            cards.parse::<Day7Part2HandType>()?
+        );
+        if let Some(hands) = self.model::<Vec<Day7Hand>>(7) {
+            let rank_in = |ranked: Vec<&Day7Hand>| {
+                ranked
+                    .iter()
+                    .position(|hand| hand.cards == cards)
+                    .map(|i| i + 1)
+            };
+            match (
+                rank_in(day7_rank_part1(hands)),
+                rank_in(day7_rank_part2(hands)),
+            ) {
+                (Some(rank1), Some(rank2)) => out.push_str(&format!(
+                    "; rank {} then {} of {}",
+                    rank1,
+                    rank2,
+                    hands.len()
+                )),
+                _ => out.push_str("; not in the input"),
+            }
+        }
+        Ok(out)
+    }
+
+    fn walk(&self, node: &str, steps: usize) -> Result<String, String> {
+        let network = self.require::<Day8Network>(8)?;
+        let path = network
+            .walk(node, steps)
+            .ok_or(format!("No such node: {}", node))?;
+        let mut out = node.to_owned();
+        for (instruction, node) in network.instructions.iter().cycle().zip(&path[1..]) {
+            let direction = match instruction {
+                Day8Direction::L => 'L',
+                Day8Direction::R => 'R',
+            };
+            out.push_str(&format!(" -{}-> {}", direction, node));
+        }
+        Ok(out)
+    }
+}
+
+/// A polynomial term like `3`, `3n` or `1/2n^2`.
+fn term((coefficient, power): &(BigRational, usize)) -> String {
+    match power {
+        0 => coefficient.to_string(),
+        1 => format!("{}n", coefficient),
+        _ => format!("{}n^{}", coefficient, power),
+    }
+}
+
+fn fit(sequence: &[i64]) -> Result<String, String> {
+    let coefficients = day9_compute_coefficients(day9_to_rational(sequence));
+    let polynomial = coefficients.iter().map(term).collect::<Vec<_>>();
+    Ok(format!(
+        "{}; next {}, previous {}",
+        polynomial.join(" + "),
+        day9_eval_coeffs_at_position(&coefficients, sequence.len() as i64),
+        day9_eval_coeffs_at_position(&coefficients, -1)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::new(InputStore::new("inputs", "example"));
+        assert_eq!(
+            session.eval("map seed 79").unwrap_err(),
+            "Needs day 5 loaded, e.g. `load 5`"
+        );
+        session.eval("load 5").unwrap();
+        assert_eq!(
+            session.eval("map seed 79").unwrap(),
+            "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 \
+             -> humidity 78 -> location 82"
+        );
+        assert_eq!(
+            session.eval("map humidity 78").unwrap(),
+            "humidity 78 -> location 82"
+        );
+        assert!(session.eval("map gold 1").is_err());
+
+        assert_eq!(
+            session.eval("rank KTJJT").unwrap(),
+            "part 1: TwoPair, part 2: FourOfAKind"
+        );
+        session.eval("load 7").unwrap();
+        assert_eq!(
+            session.eval("rank KTJJT").unwrap(),
+            "part 1: TwoPair, part 2: FourOfAKind; rank 2 then 5 of 5"
+        );
+        assert!(session.eval("rank KTJJX").is_err());
+
+        session.eval("load 8").unwrap();
+        assert_eq!(
+            session.eval("walk AAA 3").unwrap(),
+            "AAA -R-> CCC -L-> ZZZ -R-> ZZZ"
+        );
+        assert!(session.eval("walk XYZ 1").is_err());
+
+        assert_eq!(
+            session.eval("fit 0 3 6 9").unwrap(),
+            "3n; next 12, previous -3"
+        );
+        assert_eq!(
+            session.eval("fit 1 3 6 10 15 21").unwrap(),
+            "1/2n^2 + 3/2n + 1; next 28, previous 0"
+        );
+        assert!(session.eval("fit").is_err());
+        assert!(session.eval("frobnicate").is_err());
+    }
+}
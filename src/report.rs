@@ -0,0 +1,212 @@
+//! Structured output of a run, for tools that consume results rather than people.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::runner::Outcome;
+
+/// Expected answers keyed by (day, part).
+pub type Answers = HashMap<(u8, u8), String>;
+
+/// Parse an answers file: one `day part answer` line per answer. Blank lines and lines
+/// starting with `#` are ignored.
+pub fn parse_answers(inp: &str) -> Result<Answers, String> {
+    let mut answers = HashMap::new();
+    for (i, line) in inp.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = || format!("Line {}: expected `day part answer`, got: {}", i + 1, line);
+        let mut fields = line.split_ascii_whitespace();
+        let (Some(day), Some(part), Some(answer), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(err());
+        };
+        let key = (
+            day.parse().map_err(|_| err())?,
+            part.parse().map_err(|_| err())?,
+        );
+        if answers.insert(key, answer.to_owned()).is_some() {
+            return Err(format!(
+                "Line {}: second answer for day {} part {}",
+                i + 1,
+                key.0,
+                key.1
+            ));
+        }
+    }
+    Ok(answers)
+}
+
+/// SHA-256 of an input, in hex, so results can be matched up with the input they came from.
+pub fn input_checksum(inp: &str) -> String {
+    Sha256::digest(inp.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// One row of output: an outcome along with what it was expected to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The profile whose input this was, e.g. `default`.
+    pub profile: String,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Why there's no answer, e.g. `timed out`.
+    pub error: Option<String>,
+    pub expected: Option<String>,
+    /// Time spent parsing the day's input, which both parts share.
+    pub parse_duration_ns: u128,
+    pub solve_duration_ns: u128,
+    pub input_checksum: String,
+}
+
+impl Record {
+    pub fn new(profile: &str, outcome: &Outcome, expected: Option<&str>, inp: &str) -> Self {
+        Record {
+            profile: profile.to_owned(),
+            day: outcome.day,
+            part: outcome.part,
+            answer: outcome.answer.as_ref().ok().cloned(),
+            error: outcome.answer.as_ref().err().map(|e| e.to_string()),
+            expected: expected.map(|expected| expected.to_owned()),
+            parse_duration_ns: outcome.parse_duration.as_nanos(),
+            solve_duration_ns: outcome.solve_duration.as_nanos(),
+            input_checksum: input_checksum(inp),
+        }
+    }
+
+    /// Whether the answer is the expected one, or `None` if nothing was expected.
+    pub fn matches(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| self.answer.as_ref() == Some(expected))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "profile": self.profile,
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "error": self.error,
+            "expected": self.expected,
+            "match": self.matches(),
+            "parse_duration_ns": self.parse_duration_ns as u64,
+            "solve_duration_ns": self.solve_duration_ns as u64,
+            "input_checksum": self.input_checksum,
+        })
+    }
+}
+
+/// The records as a JSON array of objects. Missing values are `null`.
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(&records.iter().map(Record::to_json).collect::<Value>()).unwrap()
+}
+
+/// Quote a CSV field if it needs it, as in RFC 4180.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// The records as CSV with a header row. Missing values are empty fields.
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = "profile,day,part,answer,error,expected,match,parse_duration_ns,\
+                   solve_duration_ns,input_checksum\n"
+        .to_owned();
+    for record in records {
+        let fields = [
+            record.profile.clone(),
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.clone().unwrap_or_default(),
+            record.error.clone().unwrap_or_default(),
+            record.expected.clone().unwrap_or_default(),
+            record
+                .matches()
+                .map(|matches| matches.to_string())
+                .unwrap_or_default(),
+            record.parse_duration_ns.to_string(),
+            record.solve_duration_ns.to_string(),
+            record.input_checksum.clone(),
+        ];
+        let fields = fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{read_input, runner::RunError};
+
+    #[test]
+    fn test_answers_file() {
+        let answers = parse_answers(&read_input("inputs/default/answers.txt").unwrap()).unwrap();
+        assert_eq!(answers.len(), 18);
+        assert_eq!(answers[&(8, 2)], "14616363770447");
+        assert!(parse_answers("1 1\n").is_err());
+        assert!(parse_answers("1 1 2\n1 1 3\n").is_err());
+    }
+
+    #[test]
+    fn test_formats() {
+        let outcome = |part, answer| Outcome {
+            day: 1,
+            part,
+            answer,
+            parse_duration: Duration::from_micros(2),
+            solve_duration: Duration::from_micros(3),
+        };
+        let records = [
+            Record::new(
+                "example",
+                &outcome(1, Ok("142".to_owned())),
+                Some("142"),
+                "1abc2\n",
+            ),
+            Record::new(
+                "example",
+                &outcome(2, Err(RunError::Panicked("Line 1, \"x\"".to_owned()))),
+                None,
+                "1abc2\n",
+            ),
+        ];
+        assert_eq!(records[0].matches(), Some(true));
+        assert_eq!(records[1].matches(), None);
+
+        let checksum = input_checksum("1abc2\n");
+        assert_eq!(checksum.len(), 64);
+        assert_eq!(
+            to_csv(&records),
+            format!(
+                "profile,day,part,answer,error,expected,match,parse_duration_ns,\
+                 solve_duration_ns,input_checksum\n\
+                 example,1,1,142,,142,true,2000,3000,{checksum}\n\
+                 example,1,2,,\"panicked: Line 1, \"\"x\"\"\",,,2000,3000,{checksum}\n"
+            )
+        );
+        let json = serde_json::from_str::<Value>(&to_json(&records)).unwrap();
+        assert_eq!(json[0]["match"], true);
+        assert_eq!(json[0]["profile"], "example");
+        assert_eq!(json[1]["answer"], Value::Null);
+        assert_eq!(json[1]["input_checksum"], checksum.as_str());
+    }
+}
@@ -0,0 +1,217 @@
+//! Runs registered solutions on their inputs, several at a time.
+
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::{cancel::CancellationToken, observer::Observer, Parsed, Solution, SolveError, SolveFn};
+
+pub struct Job<'a> {
+    pub solution: &'static Solution,
+    /// Only run this part, rather than both.
+    pub part: Option<u8>,
+    pub input: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// The parser, or the solver for a part, rejected the input with this message.
+    BadInput(String),
+    /// The parser or solver panicked with this message.
+    Panicked(String),
+    /// The solver went over its time budget.
+    TimedOut,
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::BadInput(message) => write!(f, "bad input: {}", message),
+            RunError::Panicked(message) => write!(f, "panicked: {}", message),
+            RunError::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String, RunError>,
+    /// How long parsing the day's input took. Both parts share the one parse.
+    pub parse_duration: Duration,
+    pub solve_duration: Duration,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or("Solution panicked".to_owned())
+}
+
+/// Solve one part, cancelling its token once `timeout` has passed.
+fn solve_part(
+    solve: SolveFn,
+    parsed: &Parsed,
+    observer: &dyn Observer,
+    timeout: Option<Duration>,
+) -> (Result<String, RunError>, Duration) {
+    let cancel = CancellationToken::new();
+    let start = Instant::now();
+    let answer = thread::scope(|scope| {
+        let (done, wait) = mpsc::channel::<()>();
+        if let Some(timeout) = timeout {
+            let cancel = cancel.clone();
+            scope.spawn(move || {
+                // Dropping `done` disconnects the channel, so this wakes up early when
+                // the solver finishes in time
+                if let Err(mpsc::RecvTimeoutError::Timeout) = wait.recv_timeout(timeout) {
+                    cancel.cancel();
+                }
+            });
+        }
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| solve(&**parsed, observer, &cancel)));
+        drop(done);
+        answer
+    });
+    let duration = start.elapsed();
+    let answer = match answer {
+        Err(payload) => Err(RunError::Panicked(panic_message(payload))),
+        Ok(_) if cancel.is_cancelled() => Err(RunError::TimedOut),
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(SolveError::BadInput(e))) => Err(RunError::BadInput(e)),
+        Ok(Err(SolveError::Cancelled)) => unreachable!("Only the timeout cancels solvers"),
+    };
+    (answer, duration)
+}
+
+/// Parse a job's input once, then solve its parts in parallel.
+fn run_job(job: &Job, observer: &dyn Observer, timeout: Option<Duration>) -> Vec<Outcome> {
+    let parts = match job.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let start = Instant::now();
+    let parsed = panic::catch_unwind(|| (job.solution.parse)(job.input));
+    let parse_duration = start.elapsed();
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err(RunError::BadInput(e)),
+        Err(payload) => Err(RunError::Panicked(panic_message(payload))),
+    };
+    parts
+        .into_par_iter()
+        .map(|part| {
+            let (answer, solve_duration) = match &parsed {
+                Ok(parsed) => {
+                    let solve = job.solution.parts[usize::from(part) - 1];
+                    solve_part(solve, parsed, observer, timeout)
+                }
+                Err(e) => (Err(e.clone()), Duration::ZERO),
+            };
+            Outcome {
+                day: job.solution.day,
+                part,
+                answer,
+                parse_duration,
+                solve_duration,
+            }
+        })
+        .collect()
+}
+
+/// Run the jobs in parallel. The outcomes are in the same order as the jobs, and a job
+/// that fails doesn't stop the others.
+///
+/// A solver that takes longer than `timeout` is cancelled and marked as timed out.
+/// Solvers that ignore their cancellation token run to the end first, but are still
+/// marked as timed out. Parsing isn't timed out.
+pub fn run(jobs: &[Job], observer: &dyn Observer, timeout: Option<Duration>) -> Vec<Outcome> {
+    jobs.par_iter()
+        .map(|job| run_job(job, observer, timeout))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_solution, observer::Silent, read_input};
+
+    #[test]
+    fn test_run() {
+        let jobs = [
+            Job {
+                solution: find_solution(7).unwrap(),
+                part: None,
+                input: "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n",
+            },
+            Job {
+                solution: find_solution(2).unwrap(),
+                part: Some(1),
+                input: "not a game\n",
+            },
+            Job {
+                solution: find_solution(9).unwrap(),
+                part: Some(2),
+                input: "1 2\n",
+            },
+            // Parses, but part 1 can't read the spelled-out digit
+            Job {
+                solution: find_solution(1).unwrap(),
+                part: None,
+                input: "1\ntwo\n",
+            },
+        ];
+        let outcomes = run(&jobs, &Silent, None);
+        let summary = outcomes
+            .iter()
+            .map(|outcome| (outcome.day, outcome.part, outcome.answer.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (7, 1, Ok("6440".to_owned())),
+                (7, 2, Ok("5905".to_owned())),
+                (
+                    2,
+                    1,
+                    Err(RunError::BadInput("Line 1: Parsing failed".to_owned()))
+                ),
+                (9, 2, Ok("0".to_owned())),
+                (
+                    1,
+                    1,
+                    Err(RunError::BadInput("Line 2 has no digits".to_owned()))
+                ),
+                (1, 2, Ok("33".to_owned())),
+            ]
+        );
+        assert_eq!(outcomes[0].parse_duration, outcomes[1].parse_duration);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day5 = read_input("inputs/default/day5.txt").unwrap();
+        let jobs = [Job {
+            solution: find_solution(5).unwrap(),
+            part: None,
+            input: &day5,
+        }];
+        let outcomes = run(&jobs, &Silent, Some(Duration::from_millis(100)));
+        assert_eq!(outcomes[0].answer.as_deref(), Ok("389056265"));
+        assert_eq!(outcomes[1].answer, Err(RunError::TimedOut));
+        assert!(outcomes[1].solve_duration < Duration::from_secs(10));
+    }
+}
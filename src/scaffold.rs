@@ -0,0 +1,220 @@
+//! Adding a new day: a module for its solution, registered in `src/lib.rs` and tested
+//! against every profile, with placeholder inputs to fill in.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+/// The new day's module, with a parser and solvers to fill in. Until they are, the parser
+/// rejects every input, so the day fails like any bad input would rather than panicking.
+fn module(day: u8) -> String {
+    format!(
+        r#"//! Day {day}.
+
+use crate::normalise_input;
+
+/// The parsed input. Replace this with whatever fits the puzzle.
+pub type Day{day}Input = Vec<String>;
+
+pub fn day{day}_parse(inp: &str) -> Result<Day{day}Input, String> {{
+    let _inp = &*normalise_input(inp);
+    // Parse the input here, and solve it below
+    Err("Day {day} isn't solved yet".to_owned())
+}}
+
+pub fn day{day}_part1(inp: &str) -> usize {{
+    day{day}_solve_part1(&day{day}_parse(inp).unwrap())
+}}
+
+pub fn day{day}_solve_part1(_input: &Day{day}Input) -> usize {{
+    0
+}}
+
+pub fn day{day}_part2(inp: &str) -> usize {{
+    day{day}_solve_part2(&day{day}_parse(inp).unwrap())
+}}
+
+pub fn day{day}_solve_part2(_input: &Day{day}Input) -> usize {{
+    0
+}}
+"#
+    )
+}
+
+fn registry_entry(day: u8) -> String {
+    format!(
+        "    Solution::new(
+        {day},
+        |inp| boxed(day{day}::day{day}_parse(inp)),
+        [
+            |parsed, _, _| Ok(day{day}::day{day}_solve_part1(downcast(parsed)).to_string()),
+            |parsed, _, _| Ok(day{day}::day{day}_solve_part2(downcast(parsed)).to_string()),
+        ],
+    ),
+"
+    )
+}
+
+/// `src/lib.rs` with the new day's module declared, its solution registered and a test
+/// for it.
+fn wire_up(lib: &str, day: u8) -> Result<String, String> {
+    let mut lib = lib.to_owned();
+    let name = format!("day{}", day);
+
+    // Declare the module, keeping the declarations in order
+    let mods = Regex::new(r"(?m)^(?:#\[cfg\(.*\)\]\n)?(?:pub )?mod (\w+);\n").unwrap();
+    let decls = mods
+        .captures_iter(&lib)
+        .map(|caps| (caps.get(0).unwrap().range(), caps[1].to_owned()))
+        .collect::<Vec<_>>();
+    let at = decls
+        .iter()
+        .find(|(_, module)| *module > name)
+        .map(|(range, _)| range.start)
+        .or(decls.last().map(|(range, _)| range.end))
+        .ok_or("Couldn't find the module declarations in src/lib.rs")?;
+    lib.insert_str(at, &format!("pub mod {};\n", name));
+
+    // Register the solution before any later day, and make room for it
+    let count = Regex::new(r"pub const SOLUTIONS: \[Solution; (\d+)\] = \[\n").unwrap();
+    let caps = count
+        .captures(&lib)
+        .ok_or("Couldn't find SOLUTIONS in src/lib.rs")?;
+    let (count_range, list_start) = (caps.get(1).unwrap().range(), caps.get(0).unwrap().end());
+    let len = caps[1].parse::<usize>().unwrap() + 1;
+    let list_end = list_start
+        + lib[list_start..]
+            .find("\n];\n")
+            .ok_or("Couldn't find the end of SOLUTIONS in src/lib.rs")?
+        + 1;
+    let entries = Regex::new(r"(?m)^    Solution::new\(\n        (\d+),").unwrap();
+    let at = entries
+        .captures_iter(&lib[list_start..list_end])
+        .find(|caps| caps[1].parse::<u8>().is_ok_and(|d| d > day))
+        .map_or(list_end, |caps| list_start + caps.get(0).unwrap().start());
+    lib.insert_str(at, &registry_entry(day));
+    lib.replace_range(count_range, &len.to_string());
+
+    // Test it against every profile once one has answers, at the end of the tests module
+    let end = lib
+        .trim_end()
+        .strip_suffix('}')
+        .ok_or("src/lib.rs doesn't end with the tests module")?
+        .len();
+    lib.insert_str(
+        end,
+        &format!(
+            "\n    #[test]\n    fn test_{0}() {{\n        check_profiles_once_answered({1});\n    }}\n",
+            name, day
+        ),
+    );
+    Ok(lib)
+}
+
+/// Add day `day` to the crate at `root`, returning the files written. Nothing is written
+/// if the day already exists. Inputs that are already there, e.g. fetched ones, are kept.
+pub fn new_day(root: impl AsRef<Path>, day: u8) -> Result<Vec<PathBuf>, String> {
+    let root = root.as_ref();
+    if !(1..=25).contains(&day) {
+        return Err(format!("There's no day {}", day));
+    }
+    let lib_path = root.join("src/lib.rs");
+    let module_path = root.join(format!("src/day{}.rs", day));
+    let lib =
+        fs::read_to_string(&lib_path).map_err(|e| format!("{}: {}", lib_path.display(), e))?;
+    let registered = Regex::new(&format!(r"\bfn day{}_parse\b|\bmod day{};", day, day)).unwrap();
+    if module_path.exists() || registered.is_match(&lib) {
+        return Err(format!("Day {} already exists", day));
+    }
+    let lib = wire_up(&lib, day)?;
+
+    let answers_path = root.join("inputs/example/answers.txt");
+    let mut answers = match fs::read_to_string(&answers_path) {
+        Ok(answers) => answers,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("{}: {}", answers_path.display(), e)),
+    };
+    if !answers.is_empty() && !answers.ends_with('\n') {
+        answers.push('\n');
+    }
+    answers.push_str(&format!(
+        "# Day {0}: uncomment these with the answers from the puzzle description\n\
+         # {0} 1 ?\n# {0} 2 ?\n",
+        day
+    ));
+
+    let mut files = vec![
+        (module_path, module(day)),
+        (lib_path, lib),
+        (answers_path, answers),
+    ];
+    for profile in ["example", "default"] {
+        let path = root.join(format!("inputs/{}/day{}.txt", profile, day));
+        if !path.exists() {
+            files.push((path, String::new()));
+        }
+    }
+    for (path, contents) in &files {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(path, contents))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_new_day() {
+        let root = tempfile::tempdir().unwrap();
+        let lib_path = root.path().join("src/lib.rs");
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::copy("src/lib.rs", &lib_path).unwrap();
+        fs::create_dir_all(root.path().join("inputs/default")).unwrap();
+        fs::write(root.path().join("inputs/default/day24.txt"), "fetched\n").unwrap();
+
+        let files = new_day(root.path(), 24).unwrap();
+        assert_eq!(files.len(), 4);
+        let lib = fs::read_to_string(&lib_path).unwrap();
+        assert!(lib.contains("pub mod day24;\npub mod generators;\n"));
+        assert!(lib.contains(&format!("[Solution; {}]", SOLUTIONS.len() + 1)));
+        assert!(lib.contains("|inp| boxed(day24::day24_parse(inp)),"));
+        assert!(lib.ends_with("        check_profiles_once_answered(24);\n    }\n}\n"));
+        assert!(fs::read_to_string(root.path().join("src/day24.rs"))
+            .unwrap()
+            .contains("pub fn day24_solve_part2("));
+        assert_eq!(
+            fs::read_to_string(root.path().join("inputs/example/answers.txt")).unwrap(),
+            "# Day 24: uncomment these with the answers from the puzzle description\n\
+             # 24 1 ?\n# 24 2 ?\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.path().join("inputs/default/day24.txt")).unwrap(),
+            "fetched\n"
+        );
+
+        // Day 23 goes before day 24
+        new_day(root.path(), 23).unwrap();
+        let lib = fs::read_to_string(&lib_path).unwrap();
+        assert!(lib.contains(&format!("[Solution; {}]", SOLUTIONS.len() + 2)));
+        let position = |s: &str| lib.find(s).unwrap();
+        assert!(position("pub mod day23;") < position("pub mod day24;"));
+        assert!(position("boxed(day23::") < position("boxed(day24::"));
+
+        assert_eq!(
+            new_day(root.path(), 24),
+            Err("Day 24 already exists".to_owned())
+        );
+        assert_eq!(
+            new_day(root.path(), 9),
+            Err("Day 9 already exists".to_owned())
+        );
+        assert!(new_day(root.path(), 26).is_err());
+    }
+}
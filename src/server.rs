@@ -0,0 +1,142 @@
+//! A local HTTP API for the solutions, so tools in other languages can use them without
+//! linking to this crate.
+//!
+//! - `GET /days` lists the registered days.
+//! - `POST /solve/{day}/{part}` solves the input in the request body, responding with the
+//!   answer and timings, or with `{"error": ...}` and a 4xx or 5xx status.
+
+use std::{io, net::SocketAddr, thread, time::Duration};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response};
+
+use crate::{
+    find_solution,
+    observer::Silent,
+    runner::{self, Job, RunError},
+    SOLUTIONS,
+};
+
+/// Answer a request: the status code and JSON body to respond with.
+pub fn handle(method: &str, path: &str, body: &str, timeout: Option<Duration>) -> (u16, Value) {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match (method, &segments[..]) {
+        ("GET", ["days"]) => (
+            200,
+            json!({ "days": SOLUTIONS.iter().map(|solution| solution.day).collect::<Vec<_>>() }),
+        ),
+        ("POST", ["solve", day, part]) => solve(day, part, body, timeout),
+        (_, ["days"] | ["solve", _, _]) => (405, json!({ "error": "Method not allowed" })),
+        _ => (
+            404,
+            json!({ "error": format!("No such endpoint: {}", path) }),
+        ),
+    }
+}
+
+fn solve(day: &str, part: &str, inp: &str, timeout: Option<Duration>) -> (u16, Value) {
+    let Some(solution) = day.parse().ok().and_then(find_solution) else {
+        return (
+            404,
+            json!({ "error": format!("No solution for day {}", day) }),
+        );
+    };
+    let part = match part.parse::<u8>() {
+        Ok(part @ 1..=2) => part,
+        _ => return (404, json!({ "error": format!("No part {}", part) })),
+    };
+    let job = Job {
+        solution,
+        part: Some(part),
+        input: inp,
+    };
+    let outcome = runner::run(&[job], &Silent, timeout).remove(0);
+    let status = match &outcome.answer {
+        Ok(_) => 200,
+        Err(RunError::BadInput(_)) => 400,
+        Err(RunError::Panicked(_)) => 500,
+        Err(RunError::TimedOut) => 504,
+    };
+    let mut body = json!({
+        "day": outcome.day,
+        "part": outcome.part,
+        "parse_duration_ns": outcome.parse_duration.as_nanos() as u64,
+        "solve_duration_ns": outcome.solve_duration.as_nanos() as u64,
+    });
+    match outcome.answer {
+        Ok(answer) => body["answer"] = answer.into(),
+        Err(e) => body["error"] = e.to_string().into(),
+    }
+    (status, body)
+}
+
+fn respond(mut request: Request, timeout: Option<Duration>) -> io::Result<()> {
+    let mut body = String::new();
+    let (status, json) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => handle(request.method().as_str(), request.url(), &body, timeout),
+        Err(e) => (400, json!({ "error": format!("Bad body: {}", e) })),
+    };
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    request.respond(
+        Response::from_string(json.to_string())
+            .with_status_code(status)
+            .with_header(content_type),
+    )
+}
+
+/// Serves the API, answering each request on its own thread.
+pub struct Server {
+    server: tiny_http::Server,
+    timeout: Option<Duration>,
+}
+
+impl Server {
+    /// Listen on `addr`, e.g. `127.0.0.1:0` for any free port. Solutions are given up on
+    /// after `timeout`, if there is one.
+    pub fn bind(addr: &str, timeout: Option<Duration>) -> Result<Self, String> {
+        let server = tiny_http::Server::http(addr).map_err(|e| format!("{}: {}", addr, e))?;
+        Ok(Server { server, timeout })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Answer requests until the process exits.
+    pub fn serve(&self) {
+        for request in self.server.incoming_requests() {
+            let timeout = self.timeout;
+            thread::spawn(move || respond(request, timeout));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle() {
+        let (status, body) = handle("GET", "/days", "", None);
+        assert_eq!(status, 200);
+        let days = SOLUTIONS
+            .iter()
+            .map(|solution| solution.day)
+            .collect::<Vec<_>>();
+        assert_eq!(body["days"], json!(days));
+
+        let (status, body) = handle("POST", "/solve/1/1", "1abc2\npqr3stu8vwx\n", None);
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], "50");
+        assert_eq!(body["day"], 1);
+
+        let (status, body) = handle("POST", "/solve/2/1", "nonsense\n", None);
+        assert_eq!(status, 400);
+        assert_eq!(body["error"], "bad input: Line 1: Parsing failed");
+
+        assert_eq!(handle("POST", "/solve/42/1", "", None).0, 404);
+        assert_eq!(handle("POST", "/solve/1/3", "", None).0, 404);
+        assert_eq!(handle("GET", "/solve/1/1", "", None).0, 405);
+        assert_eq!(handle("GET", "/", "", None).0, 404);
+    }
+}
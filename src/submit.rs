@@ -0,0 +1,342 @@
+//! Submitting answers to the Advent of Code site, keeping a log of every attempt so that
+//! answers known to be wrong are never sent twice and the site's rate limit is respected.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use serde_json::{json, Value};
+use ureq::Agent;
+
+use crate::inputs::{agent, DEFAULT_BASE_URL, USER_AGENT, YEAR};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<Hint>),
+    /// The answer wasn't checked because the last one was too recent.
+    TooSoon,
+    /// The part is already solved, or part 2 isn't unlocked yet.
+    WrongLevel,
+    /// A response that didn't match any of the above.
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong(Some(Hint::TooHigh)) => "too high",
+            Verdict::Wrong(Some(Hint::TooLow)) => "too low",
+            Verdict::Wrong(None) => "wrong",
+            Verdict::TooSoon => "too soon",
+            Verdict::WrongLevel => "wrong level",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::Wrong(Some(Hint::TooHigh))),
+            "too low" => Ok(Verdict::Wrong(Some(Hint::TooLow))),
+            "wrong" => Ok(Verdict::Wrong(None)),
+            "too soon" => Ok(Verdict::TooSoon),
+            "wrong level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("Unknown verdict: {}", s)),
+        }
+    }
+}
+
+/// The site's response to a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long to wait before submitting again, if the site said.
+    pub wait: Option<Duration>,
+}
+
+/// Read the verdict and any wait out of the page the site returns after a submission.
+pub fn parse_response(html: &str) -> Response {
+    let verdict = if html.contains("That's the right answer") {
+        Verdict::Correct
+    } else if html.contains("That's not the right answer") {
+        Verdict::Wrong(if html.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        })
+    } else if html.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+    // e.g. "You have 1m 23s left to wait" or "please wait 5 minutes before trying again"
+    let left_to_wait = Regex::new(r"(?:(\d+)m ?)?(?:(\d+)s)? left to wait").unwrap();
+    let wait_minutes = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+    let wait = if let Some(caps) = left_to_wait.captures(html) {
+        let num = |i| {
+            caps.get(i)
+                .map_or(0, |m| m.as_str().parse::<u64>().unwrap())
+        };
+        Some(Duration::from_secs(num(1) * 60 + num(2)))
+    } else {
+        wait_minutes.captures(html).map(|caps| {
+            let minutes = caps[1].parse::<u64>().unwrap_or(1);
+            Duration::from_secs(minutes * 60)
+        })
+    };
+    Response { verdict, wait }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub response: Response,
+}
+
+impl Attempt {
+    fn to_json(&self) -> Value {
+        json!({
+            "timestamp": self.timestamp,
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "verdict": self.response.verdict.to_string(),
+            "wait_secs": self.response.wait.map(|wait| wait.as_secs()),
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Attempt {
+            timestamp: value["timestamp"].as_u64()?,
+            day: value["day"].as_u64()?.try_into().ok()?,
+            part: value["part"].as_u64()?.try_into().ok()?,
+            answer: value["answer"].as_str()?.to_owned(),
+            response: Response {
+                verdict: value["verdict"].as_str()?.parse().ok()?,
+                wait: value["wait_secs"].as_u64().map(Duration::from_secs),
+            },
+        })
+    }
+
+    /// When the site will accept another answer, as seconds since the Unix epoch.
+    fn next_allowed(&self) -> u64 {
+        self.timestamp + self.response.wait.map_or(0, |wait| wait.as_secs())
+    }
+}
+
+/// Every attempt, one JSON object per line.
+pub struct AttemptLog {
+    path: PathBuf,
+}
+
+impl AttemptLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        AttemptLog { path: path.into() }
+    }
+
+    pub fn attempts(&self) -> Result<Vec<Attempt>, String> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(format!("{}: {}", self.path.display(), e)),
+        };
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .ok()
+                    .as_ref()
+                    .and_then(Attempt::from_json)
+                    .ok_or(format!("{}: bad line {}", self.path.display(), i + 1))
+            })
+            .collect()
+    }
+
+    pub fn record(&self, attempt: &Attempt) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", attempt.to_json()))
+            .map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+
+    /// Why `answer` shouldn't be submitted at `now`, judging by earlier attempts: the
+    /// part is solved, the answer was wrong before or is on the wrong side of an answer
+    /// that was too high or too low, or the site asked us to wait.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        let attempts = self.attempts()?;
+        if let Some(last) = attempts.last() {
+            if now < last.next_allowed() {
+                return Err(format!(
+                    "The site asked to wait another {}s",
+                    last.next_allowed() - now
+                ));
+            }
+        }
+        let number = answer.parse::<i128>().ok();
+        for attempt in attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part)
+        {
+            let bound = attempt.answer.parse::<i128>().ok();
+            match (&attempt.response.verdict, number, bound) {
+                (Verdict::Correct, _, _) => {
+                    return Err(format!(
+                        "Day {} part {} is already solved with {}",
+                        day, part, attempt.answer
+                    ))
+                }
+                (Verdict::Wrong(_), _, _) if attempt.answer == answer => {
+                    return Err(format!("{} was already wrong", answer))
+                }
+                (Verdict::Wrong(Some(Hint::TooHigh)), Some(n), Some(high)) if n >= high => {
+                    return Err(format!("{} was too high, so {} is too", high, answer))
+                }
+                (Verdict::Wrong(Some(Hint::TooLow)), Some(n), Some(low)) if n <= low => {
+                    return Err(format!("{} was too low, so {} is too", low, answer))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Posts answers to the site, logged in with a session cookie, after checking them
+/// against the attempt log.
+pub struct Submitter {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    log: AttemptLog,
+}
+
+impl Submitter {
+    pub fn new(session: &str, log: AttemptLog) -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL, session, log)
+    }
+
+    /// Submit to another server, e.g. a mock one in tests.
+    pub fn with_base_url(base_url: &str, session: &str, log: AttemptLog) -> Self {
+        Submitter {
+            agent: agent(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            log,
+        }
+    }
+
+    pub fn log(&self) -> &AttemptLog {
+        &self.log
+    }
+
+    /// Submit an answer unless the log shows it's pointless, and record the response.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Response, String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.log.check(day, part, answer, now)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level = part.to_string();
+        let html = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| format!("{}: {}", url, e))?;
+        let response = parse_response(&html);
+        self.log.record(&Attempt {
+            timestamp: now,
+            day,
+            part,
+            answer: answer.to_owned(),
+            response: response.clone(),
+        })?;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let wrong = "<article><p>That's not the right answer; your answer is too high.  If \
+                     you're stuck, ...  Please wait one minute before trying again.</p></article>";
+        assert_eq!(
+            parse_response(wrong),
+            Response {
+                verdict: Verdict::Wrong(Some(Hint::TooHigh)),
+                wait: Some(Duration::from_secs(60)),
+            }
+        );
+        let too_soon = "<p>You gave an answer too recently; you have to wait after submitting \
+                        an answer before trying again.  You have 1m 23s left to wait.</p>";
+        assert_eq!(
+            parse_response(too_soon),
+            Response {
+                verdict: Verdict::TooSoon,
+                wait: Some(Duration::from_secs(83)),
+            }
+        );
+        assert_eq!(
+            parse_response("You have 35s left to wait").wait,
+            Some(Duration::from_secs(35))
+        );
+        assert_eq!(
+            parse_response("That's the right answer! You are one gold star closer").verdict,
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_response("please wait 5 minutes before trying again").wait,
+            Some(Duration::from_secs(300))
+        );
+        for verdict in [
+            "correct",
+            "too high",
+            "too low",
+            "wrong",
+            "too soon",
+            "wrong level",
+        ] {
+            assert_eq!(verdict.parse::<Verdict>().unwrap().to_string(), verdict);
+        }
+    }
+}
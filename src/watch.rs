@@ -0,0 +1,113 @@
+//! Noticing when input files change, and how answers differ between runs, so a day can
+//! be re-run on every save while it's being solved.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::report::Record;
+
+/// Polls files' modification times. Files that don't exist yet are watched too, so
+/// creating one counts as a change.
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+impl FileWatcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        FileWatcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let time = modified(&path);
+                    (path, time)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// The files that were modified, created or removed since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, time) in &mut self.files {
+            let now = modified(path);
+            if now != *time {
+                *time = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// How a record compares with the same profile, day and part in the previous run, e.g.
+/// `was 141` or `unchanged, solve was 1.20ms`. Empty on the first run.
+pub fn describe_change(previous: &[Record], record: &Record) -> String {
+    let Some(previous) = previous.iter().find(|previous| {
+        (&previous.profile, previous.day, previous.part)
+            == (&record.profile, record.day, record.part)
+    }) else {
+        return String::new();
+    };
+    let was = |record: &Record| {
+        record
+            .answer
+            .clone()
+            .or(record.error.clone())
+            .unwrap_or_default()
+    };
+    let solve = Duration::from_nanos(previous.solve_duration_ns as u64);
+    match (&previous.answer, &previous.error) == (&record.answer, &record.error) {
+        true => format!("unchanged, solve was {:.2?}", solve),
+        false => format!("was {}", was(previous)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_watcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day1.txt");
+        let mut watcher = FileWatcher::new([path.clone()]);
+        assert!(watcher.poll().is_empty());
+        fs::write(&path, "1abc2\n").unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+        assert!(watcher.poll().is_empty());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll(), vec![path]);
+    }
+
+    #[test]
+    fn test_describe_change() {
+        let record = |answer: &str| Record {
+            profile: "example".to_owned(),
+            day: 1,
+            part: 1,
+            answer: Some(answer.to_owned()),
+            error: None,
+            expected: None,
+            parse_duration_ns: 1000,
+            solve_duration_ns: 2000,
+            input_checksum: String::new(),
+        };
+        assert_eq!(describe_change(&[], &record("142")), "");
+        assert_eq!(
+            describe_change(&[record("142")], &record("142")),
+            "unchanged, solve was 2.00µs"
+        );
+        assert_eq!(describe_change(&[record("141")], &record("142")), "was 141");
+    }
+}
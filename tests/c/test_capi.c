@@ -0,0 +1,130 @@
+/* Exercises the C API on the example of every registered day that has answers, in the
+ * directory given as the first argument alongside its answers.txt. A part's own example,
+ * in dayN.partM.txt, is used instead of the day's if there is one. The second argument is
+ * how many days are registered. Run by tests/capi.rs. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc2023.h"
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    if (!ok) {
+        fprintf(stderr, "FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+/* The whole file, NUL-terminated, or NULL. Sets *len to its length. */
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        return NULL;
+    }
+    fseek(file, 0, SEEK_END);
+    long size = ftell(file);
+    fseek(file, 0, SEEK_SET);
+    char *contents = malloc(size + 1);
+    *len = fread(contents, 1, size, file);
+    contents[*len] = '\0';
+    fclose(file);
+    return contents;
+}
+
+/* Copy the expected answer for a day and part from answers.txt into answer, or return 0
+ * if there is none. */
+static int find_answer(const char *answers, int day, int part, char *answer, size_t size) {
+    char line[256];
+    const char *rest = answers;
+    while (*rest) {
+        size_t line_len = strcspn(rest, "\n");
+        if (line_len < sizeof line) {
+            memcpy(line, rest, line_len);
+            line[line_len] = '\0';
+            int line_day, line_part;
+            char line_answer[128];
+            if (sscanf(line, "%d %d %127s", &line_day, &line_part, line_answer) == 3 &&
+                line_day == day && line_part == part && strlen(line_answer) < size) {
+                strcpy(answer, line_answer);
+                return 1;
+            }
+        }
+        rest += line_len + (rest[line_len] == '\n');
+    }
+    return 0;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: %s EXAMPLES_DIR NUM_DAYS\n", argv[0]);
+        return 2;
+    }
+    char path[4096];
+    size_t len;
+    snprintf(path, sizeof path, "%s/answers.txt", argv[1]);
+    char *answers = read_file(path, &len);
+    check(answers != NULL, "read answers.txt");
+    if (!answers) {
+        return 1;
+    }
+
+    uint8_t days[32];
+    size_t num_days = aoc_days(days, sizeof days);
+    check(num_days == strtoul(argv[2], NULL, 10), "every day is registered");
+    check(aoc_days(NULL, 0) == num_days, "counting days without copying them");
+
+    for (size_t i = 0; i < num_days; i++) {
+        char expected[2][128];
+        if (!find_answer(answers, days[i], 1, expected[0], sizeof expected[0]) ||
+            !find_answer(answers, days[i], 2, expected[1], sizeof expected[1])) {
+            /* A day that's still being written */
+            continue;
+        }
+        for (int part = 1; part <= 2; part++) {
+            snprintf(path, sizeof path, "%s/day%d.part%d.txt", argv[1], days[i], part);
+            char *input = read_file(path, &len);
+            if (!input) {
+                snprintf(path, sizeof path, "%s/day%d.txt", argv[1], days[i]);
+                input = read_file(path, &len);
+            }
+            check(input != NULL, path);
+            if (!input) {
+                continue;
+            }
+            char *answer = NULL, *error = NULL;
+            int status = aoc_solve(days[i], part, input, len, &answer, &error);
+            snprintf(path, sizeof path, "day %d part %d", days[i], part);
+            check(status == 0 && error == NULL, path);
+            check(answer != NULL && strcmp(answer, expected[part - 1]) == 0, path);
+            aoc_free_string(answer);
+            aoc_free_string(error);
+            free(input);
+        }
+    }
+    free(answers);
+
+    char *answer = NULL, *error = NULL;
+    check(aoc_solve(42, 1, "", 0, &answer, &error) == -1, "day 42 is an error");
+    check(answer == NULL && error && strcmp(error, "No solution for day 42") == 0,
+          "day 42's error message");
+    aoc_free_string(error);
+    check(aoc_solve(1, 3, "1abc2\n", 6, &answer, &error) == -1, "part 3 is an error");
+    check(error && strcmp(error, "No part 3") == 0, "part 3's error message");
+    aoc_free_string(error);
+    check(aoc_solve(2, 1, "nonsense\n", 9, &answer, &error) == -1, "bad input is an error");
+    check(error && strstr(error, "Line 1") != NULL, "bad input's error message");
+    aoc_free_string(error);
+    check(aoc_solve(1, 1, "\xff\n", 2, &answer, &error) == -1, "bad UTF-8 is an error");
+    aoc_free_string(error);
+    aoc_free_string(NULL);
+
+    if (failures) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,57 @@
+//! The C API, through a C program compiled with the system's C compiler (`cc`, or `$CC` if
+//! set) against the committed header, which has to match the one the build generates.
+#![cfg(feature = "capi")]
+
+mod cdylib;
+
+use std::{env, fs, process::Command};
+
+use aoc2023::SOLUTIONS;
+
+#[test]
+fn committed_header_is_up_to_date() {
+    let generated = concat!(env!("OUT_DIR"), "/aoc2023.h");
+    let committed = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc2023.h");
+    assert!(
+        fs::read_to_string(committed).unwrap() == fs::read_to_string(generated).unwrap(),
+        "{} is out of date, update it with: cp {} {}",
+        committed,
+        generated,
+        committed
+    );
+}
+
+#[test]
+fn c_api() {
+    let library = cdylib::build();
+    let library_dir = library.parent().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let exe = dir.path().join("test_capi");
+
+    let cc = env::var("CC").unwrap_or("cc".to_owned());
+    let output = Command::new(&cc)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["-Wall", "-Werror", "-Iinclude", "tests/c/test_capi.c", "-o"])
+        .arg(&exe)
+        .arg(format!("-L{}", library_dir.display()))
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-laoc2023")
+        .output()
+        .unwrap_or_else(|e| panic!("{}: {}", cc, e));
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(&exe)
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/inputs/example"))
+        .arg(SOLUTIONS.len().to_string())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,47 @@
+//! Building the cdylib, which `cargo test` doesn't, for tests that load it from another
+//! language.
+
+use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    path::PathBuf,
+    process::Command,
+};
+
+use serde_json::Value;
+
+/// Build the cdylib with the features these tests were built with, and return its path.
+pub fn build() -> PathBuf {
+    let features = [
+        ("python", cfg!(feature = "python")),
+        ("capi", cfg!(feature = "capi")),
+    ]
+    .iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| *feature)
+    .collect::<Vec<_>>();
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--lib", "--message-format=json", "--features"])
+        .arg(features.join(","));
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let output = cargo.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["target"]["name"] == "aoc2023")
+        .flat_map(|message| message["filenames"].as_array().cloned().unwrap_or_default())
+        .filter_map(|filename| filename.as_str().map(PathBuf::from))
+        .find(|filename| {
+            filename.file_name().and_then(|name| name.to_str())
+                == Some(&format!("{}aoc2023{}", DLL_PREFIX, DLL_SUFFIX))
+        })
+        .expect("No cdylib was built")
+}
@@ -0,0 +1,45 @@
+//! Checks the fast solutions against their brute-force references on generated inputs.
+
+use aoc2023::generators::{self, Day6Options, Day8Options, Day9Options};
+use aoc2023::*;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn day6_matches_reference(
+        seed: u64,
+        // As many races and digits as part 2's joined-up numbers allow
+        (num_races, time_digits) in (1usize..=4).prop_flat_map(|n| (Just(n), 2u32..=(15 / n as u32).min(4))),
+        unwinnable_fraction in 0.0..=1.0,
+        tie_fraction in 0.0..=1.0,
+    ) {
+        let options = Day6Options { num_races, time_digits, unwinnable_fraction, tie_fraction };
+        let inp = generators::day6(seed, &options).unwrap();
+        prop_assert_eq!(day6_part1(&inp), day6_part1_reference(&inp));
+        // Simulating part 2 is only quick with up to 6 digits in the joined-up time
+        if num_races * time_digits as usize <= 6 {
+            prop_assert_eq!(day6_part2(&inp), day6_part2_reference(&inp));
+        }
+    }
+
+    #[test]
+    fn day8_matches_reference(
+        seed: u64,
+        num_instructions in 1usize..=8,
+        cycle_lengths in prop::collection::vec(1usize..=6, 1..=3),
+    ) {
+        let inp = generators::day8(seed, &Day8Options { num_instructions, cycle_lengths }).unwrap();
+        prop_assert_eq!(day8_part2(&inp), Ok(day8_part2_reference(&inp)));
+    }
+
+    #[test]
+    fn day9_matches_reference(
+        seed: u64,
+        num_lines in 1usize..=10,
+        (seq_len, max_degree) in (3usize..=21).prop_flat_map(|len| (Just(len), 0..(len - 1).min(10))),
+    ) {
+        let inp = generators::day9(seed, &Day9Options { num_lines, seq_len, max_degree }).unwrap();
+        prop_assert_eq!(day9_part1(&inp), day9_part1_reference(&inp));
+        prop_assert_eq!(day9_part2(&inp), day9_part2_reference(&inp));
+    }
+}
@@ -0,0 +1,77 @@
+//! The input store and its fetchers, against a mock server and a temporary directory.
+
+mod common;
+
+use std::fs;
+
+use aoc2023::inputs::{DirFetcher, HttpFetcher, InputStore};
+use common::MockServer;
+
+#[test]
+fn http_fetcher_caches_inputs() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/2023/day/9/input" => (200, "0 3 6 9 12 15\r\n".to_owned()),
+        _ => (404, "Not found".to_owned()),
+    });
+    let root = tempfile::tempdir().unwrap();
+    let store = InputStore::new(root.path(), "alice")
+        .with_fetcher(HttpFetcher::with_base_url(&server.base_url, "abc123"));
+
+    assert_eq!(store.get(9).unwrap(), "0 3 6 9 12 15\n");
+    assert_eq!(store.get(9).unwrap(), "0 3 6 9 12 15\n");
+    assert!(root.path().join("alice/day9.txt").exists());
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+
+    // Failed fetches aren't saved
+    assert!(store.get(8).is_err());
+    assert!(!store.path(8).exists());
+}
+
+#[test]
+fn dir_fetcher_and_existing_inputs() {
+    let downloads = tempfile::tempdir().unwrap();
+    fs::write(downloads.path().join("day1.txt"), "1abc2\n").unwrap();
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir(root.path().join("bob")).unwrap();
+    fs::write(root.path().join("bob/day2.txt"), "already here\n").unwrap();
+    let store = InputStore::new(root.path(), "bob").with_fetcher(DirFetcher::new(downloads.path()));
+
+    assert_eq!(store.get(1).unwrap(), "1abc2\n");
+    assert_eq!(
+        fs::read_to_string(root.path().join("bob/day1.txt")).unwrap(),
+        "1abc2\n"
+    );
+    assert_eq!(store.get(2).unwrap(), "already here\n");
+    assert!(store.get(3).is_err());
+
+    let without_fetcher = InputStore::new(root.path(), "bob");
+    assert_eq!(without_fetcher.get(1).unwrap(), "1abc2\n");
+    assert!(without_fetcher.get(4).is_err());
+
+    // Empty placeholders are replaced
+    fs::write(root.path().join("bob/day5.txt"), "").unwrap();
+    fs::write(downloads.path().join("day5.txt"), "seeds: 1 2\n").unwrap();
+    assert!(!store.has(5));
+    assert_eq!(store.get(5).unwrap(), "seeds: 1 2\n");
+    assert!(store.has(5));
+}
+
+#[test]
+fn part_inputs() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir(root.path().join("carol")).unwrap();
+    fs::write(root.path().join("carol/day1.txt"), "1abc2\n").unwrap();
+    fs::write(root.path().join("carol/day1.part2.txt"), "two1nine\r\n").unwrap();
+    fs::write(root.path().join("carol/day2.part2.txt"), "").unwrap();
+    fs::write(root.path().join("carol/day2.txt"), "Game 1: 3 blue\n").unwrap();
+    let store = InputStore::new(root.path(), "carol");
+
+    assert_eq!(store.get_part(1, 1).unwrap(), "1abc2\n");
+    assert_eq!(store.get_part(1, 2).unwrap(), "two1nine\n");
+    // Empty part inputs fall back to the day's, and the day's is still needed
+    assert_eq!(store.get_part(2, 2).unwrap(), "Game 1: 3 blue\n");
+    assert!(store.get_part(3, 2).is_err());
+}
@@ -0,0 +1,30 @@
+//! The Python extension module, through a local Python interpreter (`python3`, or
+//! `$PYTHON` if set).
+#![cfg(feature = "python")]
+
+mod cdylib;
+
+use std::{env, fs, process::Command};
+
+#[test]
+fn python_bindings() {
+    let library = cdylib::build();
+    let dir = tempfile::tempdir().unwrap();
+    fs::copy(&library, dir.path().join("aoc2023.so"))
+        .unwrap_or_else(|e| panic!("{}: {}", library.display(), e));
+
+    let python = env::var("PYTHON").unwrap_or("python3".to_owned());
+    let output = Command::new(&python)
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/python/test_bindings.py"
+        ))
+        .env("PYTHONPATH", dir.path())
+        .output()
+        .unwrap_or_else(|e| panic!("{}: {}", python, e));
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
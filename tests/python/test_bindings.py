@@ -0,0 +1,93 @@
+"""The `aoc2023` extension module, run by tests/python.rs with the module on the path."""
+
+import pathlib
+import unittest
+
+import aoc2023
+
+EXAMPLES = pathlib.Path(__file__).parents[2] / "inputs" / "example"
+
+
+def example(day, part=None):
+    """A part's own example if it has one, or else the day's."""
+    path = EXAMPLES / f"day{day}.part{part}.txt"
+    if part is None or not path.exists():
+        path = EXAMPLES / f"day{day}.txt"
+    return path.read_text()
+
+
+class SolveTest(unittest.TestCase):
+    def test_every_day(self):
+        answers = {}
+        for line in (EXAMPLES / "answers.txt").read_text().splitlines():
+            if not line.strip() or line.startswith("#"):
+                continue
+            day, part, answer = line.split()
+            answers[int(day), int(part)] = answer
+        days = aoc2023.days()
+        self.assertEqual(days, sorted(days))
+        self.assertEqual(days[:9], list(range(1, 10)))
+        for day in days:
+            # Days that are still being written have no answers yet
+            if (day, 1) not in answers or (day, 2) not in answers:
+                continue
+            for part in (1, 2):
+                self.assertEqual(aoc2023.solve(day, part, example(day, part)), answers[day, part])
+
+    def test_errors(self):
+        with self.assertRaisesRegex(ValueError, "No solution for day 42"):
+            aoc2023.solve(42, 1, "")
+        with self.assertRaisesRegex(ValueError, "No part 3"):
+            aoc2023.solve(1, 3, "1abc2\n")
+        with self.assertRaisesRegex(ValueError, "Line 1"):
+            aoc2023.solve(2, 1, "nonsense\n")
+
+
+class AlmanacTest(unittest.TestCase):
+    def test_trace(self):
+        almanac = aoc2023.Almanac(example(5))
+        self.assertEqual(almanac.seeds, [79, 14, 55, 13])
+        self.assertEqual(almanac.categories[0], "seed")
+        self.assertEqual(almanac.maps[0], [(50, 98, 2), (52, 50, 48)])
+        self.assertEqual(almanac.trace("humidity", 78), [("humidity", 78), ("location", 82)])
+        self.assertEqual(almanac.location(79), 82)
+        with self.assertRaises(ValueError):
+            almanac.trace("gold", 1)
+
+
+class HandTest(unittest.TestCase):
+    def test_rank(self):
+        hands = aoc2023.Hand.parse(example(7))
+        self.assertEqual(hands[3].cards, "KTJJT")
+        self.assertEqual(hands[3].bid, 220)
+        self.assertEqual(hands[3].hand_type(1), "TwoPair")
+        self.assertEqual(hands[3].hand_type(2), "FourOfAKind")
+        ranked = [hand.cards for hand in aoc2023.rank(hands, 2)]
+        self.assertEqual(ranked, ["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"])
+        with self.assertRaises(ValueError):
+            aoc2023.Hand("KTJJX")
+        with self.assertRaises(ValueError):
+            aoc2023.rank([aoc2023.Hand("AAAAA"), aoc2023.Hand("AAAAA")], 1)
+
+
+class NetworkTest(unittest.TestCase):
+    def test_walk(self):
+        network = aoc2023.Network(example(8))
+        self.assertEqual(network.instructions, "RL")
+        self.assertIn(("AAA", ("BBB", "CCC")), network.nodes)
+        self.assertEqual(network.walk("AAA", 2), ["AAA", "CCC", "ZZZ"])
+        with self.assertRaises(ValueError):
+            network.walk("XYZ", 1)
+
+
+class PolynomialTest(unittest.TestCase):
+    def test_fit(self):
+        polynomial = aoc2023.Polynomial.fit([1, 3, 6, 10, 15, 21])
+        self.assertEqual(polynomial.terms, [(1, 2, 2), (3, 2, 1), (1, 1, 0)])
+        self.assertEqual(polynomial(6), 28)
+        self.assertEqual(polynomial(-1), 0)
+        self.assertEqual(aoc2023.Polynomial.fit([10**15, 2 * 10**15])(10**6), 10**21 + 10**15)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,71 @@
+//! The HTTP API, over a real connection to localhost.
+#![cfg(feature = "server")]
+
+use std::{thread, time::Duration};
+
+use aoc2023::{server::Server, SOLUTIONS};
+use serde_json::Value;
+use ureq::Agent;
+
+fn start(timeout: Option<Duration>) -> String {
+    let server = Server::bind("127.0.0.1:0", timeout).unwrap();
+    let base_url = format!("http://{}", server.local_addr().unwrap());
+    thread::spawn(move || server.serve());
+    base_url
+}
+
+fn agent() -> Agent {
+    Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+fn post(url: &str, body: &str) -> (u16, Value) {
+    let mut response = agent().post(url).send(body).unwrap();
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().unwrap();
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+#[test]
+fn lists_days() {
+    let base_url = start(None);
+    let mut response = agent().get(format!("{}/days", base_url)).call().unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["Content-Type"].to_str().unwrap(),
+        "application/json"
+    );
+    let body = response.body_mut().read_to_string().unwrap();
+    let body = serde_json::from_str::<Value>(&body).unwrap();
+    assert_eq!(body["days"].as_array().unwrap().len(), SOLUTIONS.len());
+}
+
+#[test]
+fn solves_inputs() {
+    let base_url = start(None);
+    let (status, body) = post(
+        &format!("{}/solve/9/2", base_url),
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+    );
+    assert_eq!(status, 200);
+    assert_eq!(body["answer"], "2");
+    assert!(body["solve_duration_ns"].is_u64());
+
+    let (status, body) = post(&format!("{}/solve/8/1", base_url), "LR\n");
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().starts_with("bad input"));
+
+    let (status, _) = post(&format!("{}/solve/42/1", base_url), "");
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn times_out() {
+    let base_url = start(Some(Duration::from_millis(100)));
+    let almanac = "seeds: 0 4000000000\n\nseed-to-soil map:\n1 0 1\n";
+    let (status, body) = post(&format!("{}/solve/5/2", base_url), almanac);
+    assert_eq!(status, 504);
+    assert_eq!(body["error"], "timed out");
+}
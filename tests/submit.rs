@@ -0,0 +1,78 @@
+//! The submission client against a mock server.
+
+mod common;
+
+use aoc2023::submit::{AttemptLog, Hint, Submitter, Verdict};
+use common::MockServer;
+
+#[test]
+fn known_wrong_answers_are_not_resubmitted() {
+    let server = MockServer::start(|request| {
+        let body = match request.body.as_str() {
+            "level=1&answer=100" => "That's not the right answer; your answer is too high.",
+            "level=1&answer=10" => "That's not the right answer; your answer is too low.",
+            "level=1&answer=50" => "That's the right answer!",
+            _ => "You don't seem to be solving the right level.",
+        };
+        (
+            200,
+            format!("<main><article><p>{}</p></article></main>", body),
+        )
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let log_path = dir.path().join("attempts.jsonl");
+    let submitter =
+        Submitter::with_base_url(&server.base_url, "abc123", AttemptLog::new(&log_path));
+
+    let verdict = |answer| submitter.submit(4, 1, answer).map(|r| r.verdict);
+    assert_eq!(verdict("100"), Ok(Verdict::Wrong(Some(Hint::TooHigh))));
+    assert_eq!(verdict("10"), Ok(Verdict::Wrong(Some(Hint::TooLow))));
+    assert!(verdict("100").is_err());
+    assert!(verdict("150").is_err());
+    assert!(verdict("5").is_err());
+    assert_eq!(verdict("50"), Ok(Verdict::Correct));
+    assert!(verdict("60").is_err());
+    assert_eq!(
+        submitter.submit(4, 2, "7").map(|r| r.verdict),
+        Ok(Verdict::WrongLevel)
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2023/day/4/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+
+    // The log outlives the submitter
+    let attempts = AttemptLog::new(&log_path).attempts().unwrap();
+    let answers = attempts
+        .iter()
+        .map(|attempt| (attempt.part, attempt.answer.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(answers, [(1, "100"), (1, "10"), (1, "50"), (2, "7")]);
+}
+
+#[test]
+fn waits_are_respected() {
+    let server = MockServer::start(|_| {
+        (
+            200,
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 30s left to wait."
+                .to_owned(),
+        )
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let submitter = Submitter::with_base_url(
+        &server.base_url,
+        "abc123",
+        AttemptLog::new(dir.path().join("attempts.jsonl")),
+    );
+    assert_eq!(
+        submitter.submit(1, 1, "1").map(|r| r.verdict),
+        Ok(Verdict::TooSoon)
+    );
+    let refused = submitter.submit(1, 1, "2").unwrap_err();
+    assert!(refused.contains("wait"), "{}", refused);
+    assert_eq!(server.requests().len(), 1);
+}